@@ -0,0 +1,34 @@
+/// Primitive casting between numeric types.
+///
+/// `x.as_()` is equivalent to `x as T`, including its truncating, saturating
+/// and rounding behaviour.
+///
+/// Implemented for every pair of types covered by `Num`, so it can be used to
+/// convert between two generic `Num` types. See [`Num::cast`].
+///
+/// [`Num::cast`]: crate::Num::cast
+pub trait AsPrimitive<T: Copy>: Copy {
+    /// Converts `self` to `T` as if by `self as T`.
+    #[must_use]
+    fn as_(self) -> T;
+}
+
+macro_rules! impl_as_primitive {
+    ($($t:ty)*) => {
+        $(
+            impl_as_primitive!(@from $t => i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64);
+        )*
+    };
+    (@from $t:ty => $($u:ty)*) => {
+        $(
+            impl AsPrimitive<$u> for $t {
+                #[inline(always)]
+                fn as_(self) -> $u {
+                    self as $u
+                }
+            }
+        )*
+    };
+}
+
+impl_as_primitive!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64);
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod bit;
 mod bit_assign_ops;
 mod bit_ops;
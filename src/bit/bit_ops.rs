@@ -12,7 +12,7 @@ pub trait BitOps:
     + Shl<u32, Output = Self>
     + Shr<Output = Self>
     + Shr<u32, Output = Self>
-    + Not
+    + Not<Output = Self>
 {
 }
 
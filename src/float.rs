@@ -1,5 +1,17 @@
+mod approx;
+mod decimal;
+#[allow(clippy::module_inception)]
 mod float;
 mod float_const;
+mod hex;
+mod rounding_mode;
+mod special;
 
+pub use approx::Tolerance;
+#[doc(hidden)]
+pub use approx::{assert_approx_eq as __assert_approx_eq, ApproxOperand as __ApproxOperand};
+pub use decimal::{FloatBuf, FloatDisplay, FloatFormat};
 pub use float::Float;
 pub use float_const::FloatConst;
+pub use hex::HexFloatBuf;
+pub use rounding_mode::RoundingMode;
@@ -0,0 +1,319 @@
+use std::{fmt, slice};
+
+use crate::{Float, Num, NumOrd, Signed};
+
+/// Tolerances for comparing `Float`s that may differ by rounding errors.
+///
+/// Two finite values are approximately equal if they are within any one of
+/// the tolerances: an absolute difference, a difference relative to the
+/// larger magnitude, or a distance in units in the last place.
+///
+/// `Tolerance::exact` starts with all of them at zero, so only exactly equal
+/// values match until some are set, while `Tolerance::default` accepts
+/// values up to 4 ulps apart.
+///
+/// See [`Float::approx_eq`].
+///
+/// [`Float::approx_eq`]: crate::Float::approx_eq
+#[derive(Clone, Copy, PartialEq)]
+pub struct Tolerance<F: Float> {
+    abs: F,
+    rel: F,
+    ulps: F::Bits,
+}
+
+impl<F: Float> Tolerance<F> {
+    /// Creates tolerances that only accept exactly equal values.
+    ///
+    /// Unlike `Tolerance::default`, this accepts no rounding error at all.
+    #[must_use]
+    pub fn exact() -> Self {
+        Self {
+            abs: F::ZERO,
+            rel: F::ZERO,
+            ulps: F::Bits::ZERO,
+        }
+    }
+
+    /// Sets the largest accepted absolute difference, `|a - b|`.
+    #[must_use]
+    pub fn abs(mut self, abs: F) -> Self {
+        self.abs = abs;
+        self
+    }
+
+    /// Sets the largest accepted difference relative to the larger
+    /// magnitude, `|a - b| / max(|a|, |b|)`.
+    #[must_use]
+    pub fn rel(mut self, rel: F) -> Self {
+        self.rel = rel;
+        self
+    }
+
+    /// Sets the largest accepted number of representable values between the
+    /// two, as returned by `Float::ulps_between`.
+    #[must_use]
+    pub fn ulps(mut self, ulps: F::Bits) -> Self {
+        self.ulps = ulps;
+        self
+    }
+
+    /// Returns whether `a` and `b` are equal within these tolerances.
+    ///
+    /// NaN is never equal to anything, and infinities are only equal to
+    /// themselves.
+    #[must_use]
+    pub fn accepts(&self, a: F, b: F) -> bool {
+        if a == b {
+            return true;
+        } else if !a.is_finite() || !b.is_finite() {
+            return false;
+        }
+
+        let diff = Signed::abs(a - b);
+
+        diff <= self.abs
+            || diff <= self.rel * NumOrd::max(Signed::abs(a), Signed::abs(b))
+            || a.ulps_between(b) <= self.ulps
+    }
+}
+
+impl<F: Float> Default for Tolerance<F> {
+    /// Creates tolerances that accept values up to 4 ulps apart.
+    fn default() -> Self {
+        Self::exact().ulps(4.cast())
+    }
+}
+
+impl<F: Float + fmt::Debug> fmt::Debug for Tolerance<F>
+where
+    F::Bits: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tolerance")
+            .field("abs", &self.abs)
+            .field("rel", &self.rel)
+            .field("ulps", &self.ulps)
+            .finish()
+    }
+}
+
+/// A float or collection of floats accepted by `assert_approx_eq!`.
+#[doc(hidden)]
+pub trait ApproxOperand {
+    type Float: Float + fmt::Debug;
+
+    fn as_floats(&self) -> &[Self::Float];
+}
+
+impl<F: Float + fmt::Debug> ApproxOperand for [F] {
+    type Float = F;
+
+    fn as_floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<F: Float + fmt::Debug, const N: usize> ApproxOperand for [F; N] {
+    type Float = F;
+
+    fn as_floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<F: Float + fmt::Debug> ApproxOperand for Vec<F> {
+    type Float = F;
+
+    fn as_floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<T: ApproxOperand + ?Sized> ApproxOperand for &T {
+    type Float = T::Float;
+
+    fn as_floats(&self) -> &[T::Float] {
+        (**self).as_floats()
+    }
+}
+
+macro_rules! impl_approx_operand {
+    ($($t:ty)*) => {
+        $(
+            impl ApproxOperand for $t {
+                type Float = Self;
+
+                fn as_floats(&self) -> &[Self] {
+                    slice::from_ref(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_approx_operand!(f32 f64);
+
+/// The implementation of `assert_approx_eq!` and `assert_ulps_eq!`.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_approx_eq<A, B>(
+    left: &A,
+    right: &B,
+    tolerance: impl FnOnce(Tolerance<A::Float>) -> Tolerance<A::Float>,
+) where
+    A: ApproxOperand + ?Sized,
+    B: ApproxOperand<Float = A::Float> + ?Sized,
+    <A::Float as Float>::Bits: fmt::Debug,
+{
+    // Taking the tolerances as a closure lets their types be inferred from
+    // the operands.
+    let tolerance = tolerance(Tolerance::exact());
+    let (left, right) = (left.as_floats(), right.as_floats());
+
+    assert!(
+        left.len() == right.len(),
+        "assertion `left ≈ right` failed: lengths differ\n  left: {}\n right: {}",
+        left.len(),
+        right.len(),
+    );
+
+    for (i, (&a, &b)) in left.iter().zip(right).enumerate() {
+        if !tolerance.accepts(a, b) {
+            let at = if left.len() == 1 {
+                String::new()
+            } else {
+                format!(" at index {i}")
+            };
+
+            panic!(
+                "assertion `left ≈ right` failed{at}\n  left: {a:?}\n right: {b:?}\n  ulps: {:?}\n  with: {tolerance:?}",
+                a.ulps_between(b),
+            );
+        }
+    }
+}
+
+/// Asserts that two floats, or two slices, arrays or vectors of floats
+/// element-wise, are approximately equal.
+///
+/// Takes optional `abs`, `rel` and `ulps` tolerances as in [`Tolerance`],
+/// defaulting to 4 ulps if none are given, such as
+/// `assert_approx_eq!(a, b, abs = 1e-9, rel = 1e-6)`. Tolerances may be of
+/// any `Num` type and are converted as if by `as`.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__assert_approx_eq(&$left, &$right, |_| $crate::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $($tolerance:ident = $value:expr),+ $(,)?) => {
+        $crate::__assert_approx_eq(
+            &$left,
+            &$right,
+            |t| t$(.$tolerance($crate::Num::cast($value)))+,
+        )
+    };
+}
+
+/// Asserts that two floats, or two slices, arrays or vectors of floats
+/// element-wise, are within a number of ulps of each other.
+///
+/// The number of ulps may be of any `Num` type and defaults to 4, such as
+/// `assert_ulps_eq!(a, b, 16)`.
+#[macro_export]
+macro_rules! assert_ulps_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_ulps_eq!($left, $right, 4)
+    };
+    ($left:expr, $right:expr, $ulps:expr $(,)?) => {
+        $crate::__assert_approx_eq(&$left, &$right, |t| t.ulps($crate::Num::cast($ulps)))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Float, Tolerance};
+
+    #[test]
+    fn exact_accepts_only_equal_values() {
+        let t = Tolerance::<f64>::exact();
+
+        assert!(t.accepts(1.0, 1.0));
+        assert!(t.accepts(0.0, -0.0));
+        assert!(!t.accepts(1.0, 1.0_f64.next_up()));
+    }
+
+    #[test]
+    fn default_accepts_four_ulps() {
+        let t = Tolerance::<f32>::default();
+        let four = f32::from_bits(1.0_f32.to_bits() + 4);
+        let five = f32::from_bits(1.0_f32.to_bits() + 5);
+
+        assert!(t.accepts(1.0, four));
+        assert!(!t.accepts(1.0, five));
+    }
+
+    #[test]
+    fn abs_and_rel() {
+        let abs = Tolerance::exact().abs(0.5);
+        assert!(abs.accepts(1.0, 1.5));
+        assert!(!abs.accepts(1.0, 1.6));
+
+        let rel = Tolerance::exact().rel(0.1);
+        assert!(rel.accepts(100.0, 110.0));
+        assert!(!rel.accepts(100.0, 112.0));
+    }
+
+    #[test]
+    fn non_finite() {
+        let t = Tolerance::exact().abs(f64::MAX).ulps(u64::MAX);
+
+        assert!(!t.accepts(f64::NAN, f64::NAN));
+        assert!(!t.accepts(f64::NAN, 1.0));
+        assert!(t.accepts(f64::INFINITY, f64::INFINITY));
+        assert!(!t.accepts(f64::INFINITY, f64::MAX));
+        assert!(!t.accepts(f64::INFINITY, f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn approx_eq() {
+        assert!(0.1_f64.approx_eq(0.1_f64.next_up(), Tolerance::default()));
+        assert!(!0.1_f64.approx_eq(0.2, Tolerance::default()));
+    }
+
+    #[test]
+    fn macros_on_scalars() {
+        assert_approx_eq!(0.1 + 0.2, 0.3);
+        assert_approx_eq!(1.0_f32, 1.001_f32, abs = 0.01);
+        assert_approx_eq!(100.0, 101.0, rel = 0.01, abs = 0);
+        assert_ulps_eq!(0.1 + 0.2, 0.3);
+        assert_ulps_eq!(1.0, 1.0_f64.next_up().next_up(), 2);
+    }
+
+    #[test]
+    fn macros_on_collections() {
+        let v = vec![1.0, 2.0, 0.1 + 0.2];
+
+        assert_approx_eq!(v, [1.0, 2.0, 0.3]);
+        assert_approx_eq!(v[..], vec![1.0, 2.0, 0.3], ulps = 1);
+        assert_ulps_eq!(&v, &[1.0, 2.0, 0.3][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed at index 1")]
+    fn macros_report_index() {
+        assert_approx_eq!([1.0, 2.0], [1.0, 2.1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "lengths differ")]
+    fn macros_check_lengths() {
+        assert_ulps_eq!([1.0_f32, 2.0], [1.0_f32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left ≈ right` failed")]
+    fn macros_reject_nan() {
+        assert_ulps_eq!(f64::NAN, f64::NAN, u64::MAX);
+    }
+}
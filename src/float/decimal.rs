@@ -0,0 +1,398 @@
+mod bignum;
+mod dragon;
+mod grisu;
+mod lemire;
+mod parse;
+mod slow;
+mod table;
+
+use std::{fmt, ops::Deref, str};
+
+use crate::{Float, Num};
+
+pub(crate) use parse::parse_prefix;
+
+/// The maximum number of significant digits needed by the shortest
+/// representation of any `f64`.
+const MAX_SIG_DIGITS: usize = 17;
+
+/// A finite, non-zero float decoded as `mant * 2^exp`, along with the interval
+/// `(mant - minus) * 2^exp` to `(mant + plus) * 2^exp` of values that round
+/// to it.
+pub(crate) struct Decoded {
+    mant: u64,
+    minus: u64,
+    plus: u64,
+    exp: i32,
+
+    /// Whether the interval's boundaries themselves round to the value.
+    inclusive: bool,
+}
+
+/// The possible outcomes of decoding a float, ignoring its sign.
+pub(crate) enum FullDecoded {
+    Nan,
+    Infinite,
+    Zero,
+    Finite(Decoded),
+}
+
+/// Decodes `v` into its sign and magnitude.
+pub(crate) fn decode<F: Float>(v: F) -> (bool, FullDecoded) {
+    let (sign, biased, frac) = v.decompose();
+    let (biased, frac) = (biased.cast::<u64>(), frac.cast::<u64>());
+    let frac_bits = F::MANTISSA_BITS;
+    let exp_bits = F::EXPONENT_BITS;
+
+    let negative = sign != F::Bits::ZERO;
+
+    // The exponent of the integral significand. Subnormals use the same
+    // exponent as the smallest normals.
+    let exp = biased.max(1) as i32 + F::MIN_EXP - F::MANTISSA_DIGITS as i32 - 1;
+    let inclusive = frac & 1 == 0;
+
+    let decoded = if biased == (1 << exp_bits) - 1 {
+        if frac == 0 {
+            FullDecoded::Infinite
+        } else {
+            FullDecoded::Nan
+        }
+    } else if biased == 0 && frac == 0 {
+        FullDecoded::Zero
+    } else if biased == 0 {
+        // Subnormals lack the implicit bit; the spacing either side is one unit.
+        FullDecoded::Finite(Decoded {
+            mant: frac << 1,
+            minus: 1,
+            plus: 1,
+            exp: exp - 1,
+            inclusive,
+        })
+    } else if frac == 0 && biased > 1 {
+        // The next value down is half as far as the next value up.
+        FullDecoded::Finite(Decoded {
+            mant: (frac | (1 << frac_bits)) << 2,
+            minus: 1,
+            plus: 2,
+            exp: exp - 2,
+            inclusive,
+        })
+    } else {
+        FullDecoded::Finite(Decoded {
+            mant: (frac | (1 << frac_bits)) << 1,
+            minus: 1,
+            plus: 1,
+            exp: exp - 1,
+            inclusive,
+        })
+    };
+
+    (negative, decoded)
+}
+
+/// Rounds the decimal digits up by one in the last place.
+///
+/// Returns `true` if all digits were nines, in which case they become `100...`.
+fn round_up(digits: &mut [u8]) -> bool {
+    match digits.iter().rposition(|&c| c != b'9') {
+        Some(i) => {
+            digits[i] += 1;
+            digits[i + 1..].fill(b'0');
+            false
+        }
+        None => {
+            digits[0] = b'1';
+            digits[1..].fill(b'0');
+            true
+        }
+    }
+}
+
+/// How to format a `Float` as a decimal string.
+///
+/// The output matches the corresponding `core::fmt` format specification on
+/// current versions of Rust, but is defined by this crate and will not change
+/// with them.
+///
+/// See [`Float::to_decimal`].
+///
+/// [`Float::to_decimal`]: crate::Float::to_decimal
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FloatFormat {
+    /// The shortest digits that round-trip, in positional notation, like `{}`.
+    Shortest,
+
+    /// The shortest digits that round-trip, in scientific notation, like `{:e}`.
+    Scientific,
+
+    /// The given number of digits after the decimal point, in positional
+    /// notation, like `{:.N}`.
+    Fixed(u32),
+
+    /// The given number of digits after the decimal point, in scientific
+    /// notation, like `{:.Ne}`.
+    ScientificFixed(u32),
+}
+
+/// A stack buffer holding a `Float` formatted as a decimal string.
+///
+/// Dereferences to the formatted `str`.
+#[derive(Clone, Copy)]
+pub struct FloatBuf {
+    buf: [u8; FloatBuf::CAPACITY],
+    len: usize,
+}
+
+impl FloatBuf {
+    /// The largest precision accepted by `FloatFormat::Fixed` and
+    /// `FloatFormat::ScientificFixed`, enough to write any `f64` exactly.
+    pub const MAX_PRECISION: u32 = 1074;
+
+    /// The maximum length of any formatted `Float`: a sign, the 309 integral
+    /// digits of `f64::MAX`, a point and `MAX_PRECISION` fractional digits.
+    pub const CAPACITY: usize = 311 + Self::MAX_PRECISION as usize;
+
+    /// Returns the formatted value as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only ASCII signs, digits and letters are ever written to the buffer.
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    /// Returns the formatted value as a byte slice.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub(crate) fn new<F: Float>(v: F, format: FloatFormat) -> Self {
+        if let FloatFormat::Fixed(precision) | FloatFormat::ScientificFixed(precision) = format {
+            assert!(
+                precision <= Self::MAX_PRECISION,
+                "precision must not be greater than `FloatBuf::MAX_PRECISION`"
+            );
+        }
+
+        let mut w = Self {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+        };
+
+        let (negative, decoded) = decode(v);
+        let d = match decoded {
+            FullDecoded::Nan => {
+                w.push_bytes(b"NaN");
+                return w;
+            }
+            FullDecoded::Infinite => {
+                if negative {
+                    w.push(b'-');
+                }
+                w.push_bytes(b"inf");
+                return w;
+            }
+            FullDecoded::Zero => {
+                if negative {
+                    w.push(b'-');
+                }
+                w.push_zero(format);
+                return w;
+            }
+            FullDecoded::Finite(d) => d,
+        };
+
+        if negative {
+            w.push(b'-');
+        }
+
+        let mut digits = [0; Self::CAPACITY];
+        match format {
+            FloatFormat::Shortest => {
+                let (len, k) = format_shortest(&d, &mut digits);
+                w.push_positional(&digits[..len], k);
+            }
+            FloatFormat::Scientific => {
+                let (len, k) = format_shortest(&d, &mut digits);
+                w.push_scientific(&digits[..len], k);
+            }
+            FloatFormat::Fixed(precision) => {
+                let limit = -(precision as i32);
+                let (len, k) = dragon::format_exact(d.mant, d.exp, &mut digits, limit);
+                w.push_fixed(&digits[..len], k, precision as usize);
+            }
+            FloatFormat::ScientificFixed(precision) => {
+                let digits = &mut digits[..precision as usize + 1];
+                let (len, k) = dragon::format_exact(d.mant, d.exp, digits, i32::MIN);
+                w.push_scientific(&digits[..len], k);
+            }
+        }
+
+        w
+    }
+
+    fn push(&mut self, b: u8) {
+        self.buf[self.len] = b;
+        self.len += 1;
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    fn push_zeros(&mut self, n: usize) {
+        self.buf[self.len..self.len + n].fill(b'0');
+        self.len += n;
+    }
+
+    fn push_zero(&mut self, format: FloatFormat) {
+        self.push(b'0');
+
+        match format {
+            FloatFormat::Shortest => {}
+            FloatFormat::Scientific => self.push_bytes(b"e0"),
+            FloatFormat::Fixed(0) => {}
+            FloatFormat::Fixed(precision) => {
+                self.push(b'.');
+                self.push_zeros(precision as usize);
+            }
+            FloatFormat::ScientificFixed(precision) => {
+                if precision > 0 {
+                    self.push(b'.');
+                    self.push_zeros(precision as usize);
+                }
+                self.push_bytes(b"e0");
+            }
+        }
+    }
+
+    /// Writes `0.digits * 10^k` without an exponent.
+    fn push_positional(&mut self, digits: &[u8], k: i32) {
+        if k <= 0 {
+            self.push_bytes(b"0.");
+            self.push_zeros(k.unsigned_abs() as usize);
+            self.push_bytes(digits);
+        } else if (k as usize) < digits.len() {
+            let (int, frac) = digits.split_at(k as usize);
+            self.push_bytes(int);
+            self.push(b'.');
+            self.push_bytes(frac);
+        } else {
+            self.push_bytes(digits);
+            self.push_zeros(k as usize - digits.len());
+        }
+    }
+
+    /// Writes `0.digits * 10^k` without an exponent and with exactly
+    /// `precision` fractional digits, where `digits` has no more than that.
+    fn push_fixed(&mut self, digits: &[u8], k: i32, precision: usize) {
+        let (frac, leading_zeros) = if k <= 0 {
+            self.push(b'0');
+            (digits, (k.unsigned_abs() as usize).min(precision))
+        } else {
+            let int = (k as usize).min(digits.len());
+            self.push_bytes(&digits[..int]);
+            self.push_zeros(k as usize - int);
+            (&digits[int..], 0)
+        };
+
+        if precision > 0 {
+            self.push(b'.');
+            self.push_zeros(leading_zeros);
+            self.push_bytes(frac);
+            self.push_zeros(precision - leading_zeros - frac.len());
+        }
+    }
+
+    /// Writes `0.digits * 10^k` as `d.ddde(k-1)`.
+    fn push_scientific(&mut self, digits: &[u8], k: i32) {
+        self.push(digits[0]);
+        if digits.len() > 1 {
+            self.push(b'.');
+            self.push_bytes(&digits[1..]);
+        }
+
+        self.push(b'e');
+        let exp = k - 1;
+        if exp < 0 {
+            self.push(b'-');
+        }
+
+        let mut exp = exp.unsigned_abs();
+        let mut buf = [0; 10];
+        let mut i = buf.len();
+        loop {
+            i -= 1;
+            buf[i] = b'0' + (exp % 10) as u8;
+            exp /= 10;
+
+            if exp == 0 {
+                break;
+            }
+        }
+        self.push_bytes(&buf[i..]);
+    }
+}
+
+/// Writes the shortest digits that round to `d`, trying Grisu before Dragon.
+fn format_shortest(d: &Decoded, buf: &mut [u8]) -> (usize, i32) {
+    match grisu::format_shortest_opt(d, buf) {
+        Some(r) => r,
+        None => dragon::format_shortest(d, buf),
+    }
+}
+
+impl Deref for FloatBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FloatBuf {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for FloatBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for FloatBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for FloatBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// A `core::fmt` adapter that formats a `Float` as a decimal string.
+///
+/// See [`Float::display_decimal`].
+///
+/// [`Float::display_decimal`]: crate::Float::display_decimal
+#[derive(Clone, Copy, Debug)]
+pub struct FloatDisplay<F: Float> {
+    value: F,
+    format: FloatFormat,
+}
+
+impl<F: Float> FloatDisplay<F> {
+    pub(crate) fn new(value: F, format: FloatFormat) -> Self {
+        Self { value, format }
+    }
+}
+
+impl<F: Float> fmt::Display for FloatDisplay<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FloatBuf::new(self.value, self.format).fmt(f)
+    }
+}
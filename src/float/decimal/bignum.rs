@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+
+/// The number of 32-bit limbs, enough for any `f64` scaled by a power of ten.
+const LIMBS: usize = 40;
+
+/// A fixed-size, stack allocated unsigned big integer.
+///
+/// Only implements what the digit generation algorithms need.
+#[derive(Clone, Copy)]
+pub(crate) struct Big {
+    /// The number of limbs in use. Limbs at and above this are zero.
+    size: usize,
+
+    /// Little-endian limbs.
+    base: [u32; LIMBS],
+}
+
+impl Big {
+    pub(crate) fn from_u64(mut v: u64) -> Self {
+        let mut base = [0; LIMBS];
+        let mut size = 0;
+
+        while v > 0 {
+            base[size] = v as u32;
+            v >>= 32;
+            size += 1;
+        }
+
+        Self { size, base }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.base[..self.size].iter().all(|&x| x == 0)
+    }
+
+    pub(crate) fn add(&mut self, other: &Self) -> &mut Self {
+        let size = self.size.max(other.size);
+        let mut carry = false;
+
+        for i in 0..size {
+            let (v, c1) = self.base[i].overflowing_add(other.base[i]);
+            let (v, c2) = v.overflowing_add(u32::from(carry));
+            self.base[i] = v;
+            carry = c1 || c2;
+        }
+
+        self.size = size;
+        if carry {
+            self.base[size] = 1;
+            self.size += 1;
+        }
+
+        self
+    }
+
+    /// Subtracts `other`, which must not be greater than `self`.
+    pub(crate) fn sub(&mut self, other: &Self) -> &mut Self {
+        let mut borrow = false;
+
+        for i in 0..self.size {
+            let (v, b1) = self.base[i].overflowing_sub(other.base[i]);
+            let (v, b2) = v.overflowing_sub(u32::from(borrow));
+            self.base[i] = v;
+            borrow = b1 || b2;
+        }
+
+        debug_assert!(!borrow, "bignum subtraction underflowed");
+        self
+    }
+
+    pub(crate) fn mul_small(&mut self, m: u32) -> &mut Self {
+        let mut carry = 0;
+
+        for x in &mut self.base[..self.size] {
+            let v = u64::from(*x) * u64::from(m) + carry;
+            *x = v as u32;
+            carry = v >> 32;
+        }
+
+        if carry > 0 {
+            self.base[self.size] = carry as u32;
+            self.size += 1;
+        }
+
+        self
+    }
+
+    pub(crate) fn mul_pow2(&mut self, bits: u32) -> &mut Self {
+        let limbs = (bits / 32) as usize;
+        let bits = bits % 32;
+
+        if limbs > 0 {
+            self.base.copy_within(..self.size, limbs);
+            self.base[..limbs].fill(0);
+            self.size += limbs;
+        }
+
+        if bits > 0 && self.size > 0 {
+            let top = self.base[self.size - 1] >> (32 - bits);
+            for i in (limbs + 1..self.size).rev() {
+                self.base[i] = (self.base[i] << bits) | (self.base[i - 1] >> (32 - bits));
+            }
+            self.base[limbs] <<= bits;
+
+            if top > 0 {
+                self.base[self.size] = top;
+                self.size += 1;
+            }
+        }
+
+        self
+    }
+
+    pub(crate) fn mul_pow5(&mut self, mut e: u32) -> &mut Self {
+        // 5^13 is the largest power of five that fits in a limb.
+        const POW5_13: u32 = 1_220_703_125;
+
+        while e >= 13 {
+            self.mul_small(POW5_13);
+            e -= 13;
+        }
+
+        self.mul_small(5_u32.pow(e))
+    }
+
+    pub(crate) fn mul_pow10(&mut self, e: u32) -> &mut Self {
+        self.mul_pow5(e).mul_pow2(e)
+    }
+}
+
+impl PartialEq for Big {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Big {}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let size = self.size.max(other.size);
+
+        self.base[..size]
+            .iter()
+            .rev()
+            .cmp(other.base[..size].iter().rev())
+    }
+}
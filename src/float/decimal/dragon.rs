@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+
+use super::{bignum::Big, round_up, Decoded};
+
+/// Estimates `k` such that `10^(k-1) < mant * 2^exp <= 10^(k+1)`.
+fn estimate_scaling_factor(mant: u64, exp: i32) -> i32 {
+    let nbits = 64 - i64::from((mant - 1).leading_zeros());
+
+    // 1292913986 is `floor(log10(2) * 2^32)`.
+    (((nbits + i64::from(exp)) * 1_292_913_986) >> 32) as i32
+}
+
+/// Returns `mant * 2^exp` and `1` as big integers.
+fn scaled(mant: u64, exp: i32) -> (Big, Big) {
+    let mut mant = Big::from_u64(mant);
+    let mut scale = Big::from_u64(1);
+
+    if exp < 0 {
+        scale.mul_pow2(exp.unsigned_abs());
+    } else {
+        mant.mul_pow2(exp as u32);
+    }
+
+    (mant, scale)
+}
+
+fn sum(a: &Big, b: &Big) -> Big {
+    let mut sum = *a;
+    sum.add(b);
+    sum
+}
+
+/// Divides `x` by `scale` where the quotient is known to be less than 16.
+fn div_rem_upto_16(x: &mut Big, scale: &Big, scale2: &Big, scale4: &Big, scale8: &Big) -> u8 {
+    let mut d = 0;
+
+    if *x >= *scale8 {
+        x.sub(scale8);
+        d += 8;
+    }
+    if *x >= *scale4 {
+        x.sub(scale4);
+        d += 4;
+    }
+    if *x >= *scale2 {
+        x.sub(scale2);
+        d += 2;
+    }
+    if *x >= *scale {
+        x.sub(scale);
+        d += 1;
+    }
+
+    debug_assert!(*x < *scale);
+    d
+}
+
+/// Returns `scale` multiplied by 2, 4 and 8.
+fn scale_multiples(scale: &Big) -> (Big, Big, Big) {
+    let mut scale2 = *scale;
+    scale2.mul_pow2(1);
+    let mut scale4 = *scale;
+    scale4.mul_pow2(2);
+    let mut scale8 = *scale;
+    scale8.mul_pow2(3);
+
+    (scale2, scale4, scale8)
+}
+
+/// Writes the shortest digits that round to `d` into `buf` with Steele &
+/// White's Dragon4.
+///
+/// Always correct but slower than Grisu, which falls back to this. Returns
+/// the number of digits and `k` such that the value is `0.digits * 10^k`.
+pub(crate) fn format_shortest(d: &Decoded, buf: &mut [u8]) -> (usize, i32) {
+    // With an inclusive interval the boundaries round to `d` as well.
+    let rounding = if d.inclusive {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    };
+
+    let mut k = estimate_scaling_factor(d.mant + d.plus, d.exp);
+
+    let (mut mant, mut scale) = scaled(d.mant, d.exp);
+    let (mut minus, _) = scaled(d.minus, d.exp);
+    let (mut plus, _) = scaled(d.plus, d.exp);
+
+    if k >= 0 {
+        scale.mul_pow10(k as u32);
+    } else {
+        mant.mul_pow10(k.unsigned_abs());
+        minus.mul_pow10(k.unsigned_abs());
+        plus.mul_pow10(k.unsigned_abs());
+    }
+
+    // The estimate may be one too small; rather than scaling `scale` by 10
+    // when it is, skip the first multiplication of everything else.
+    if scale.cmp(&sum(&mant, &plus)) < rounding {
+        k += 1;
+    } else {
+        mant.mul_small(10);
+        minus.mul_small(10);
+        plus.mul_small(10);
+    }
+
+    let (scale2, scale4, scale8) = scale_multiples(&scale);
+
+    let mut i = 0;
+    let (down, up) = loop {
+        buf[i] = b'0' + div_rem_upto_16(&mut mant, &scale, &scale2, &scale4, &scale8);
+        i += 1;
+
+        // Stop once the digits so far, rounded either way, are in the interval.
+        let down = mant.cmp(&minus) < rounding;
+        let up = scale.cmp(&sum(&mant, &plus)) < rounding;
+        if down || up {
+            break (down, up);
+        }
+
+        mant.mul_small(10);
+        minus.mul_small(10);
+        plus.mul_small(10);
+    };
+
+    // Round up if only that is in the interval, or if both are and the
+    // remaining digits are at least one half.
+    if up && (!down || *mant.mul_pow2(1) >= scale) && round_up(&mut buf[..i]) {
+        i = 1;
+        k += 1;
+    }
+
+    (i, k)
+}
+
+/// Writes the digits of `mant * 2^exp`, correctly rounded with ties to even,
+/// into `buf`.
+///
+/// Stops after `buf.len()` digits or the digit at `10^limit`, whichever comes
+/// first. Returns the number of digits and `k` such that the value is
+/// `0.digits * 10^k`.
+pub(crate) fn format_exact(mant: u64, exp: i32, buf: &mut [u8], limit: i32) -> (usize, i32) {
+    let mut k = estimate_scaling_factor(mant, exp);
+
+    let (mut mant, mut scale) = scaled(mant, exp);
+
+    if k >= 0 {
+        scale.mul_pow10(k as u32);
+    } else {
+        mant.mul_pow10(k.unsigned_abs());
+    }
+
+    if mant >= scale {
+        k += 1;
+    } else {
+        mant.mul_small(10);
+    }
+
+    // Everything is below half of `10^limit`, so the result rounds to zero.
+    if k < limit {
+        return (0, limit);
+    }
+
+    let mut len = (i64::from(k) - i64::from(limit)).min(buf.len() as i64) as usize;
+
+    let (scale2, scale4, scale8) = scale_multiples(&scale);
+
+    for i in 0..len {
+        if mant.is_zero() {
+            buf[i..len].fill(b'0');
+            break;
+        }
+
+        buf[i] = b'0' + div_rem_upto_16(&mut mant, &scale, &scale2, &scale4, &scale8);
+        mant.mul_small(10);
+    }
+
+    // The remainder is now scaled by 10, so one half is `5 * scale`.
+    let order = mant.cmp(scale.mul_small(5));
+    let odd = len > 0 && buf[len - 1] & 1 == 1;
+
+    if order == Ordering::Greater || (order == Ordering::Equal && odd) {
+        if len == 0 {
+            // Rounds up to `10^limit`.
+            buf[0] = b'1';
+            return (1, k + 1);
+        }
+
+        if round_up(&mut buf[..len]) {
+            // All digits were nines and became `100...`, which may need one
+            // more zero to reach `limit`.
+            k += 1;
+            if i64::from(k) - i64::from(limit) > len as i64 && len < buf.len() {
+                buf[len] = b'0';
+                len += 1;
+            }
+        }
+    }
+
+    (len, k)
+}
@@ -0,0 +1,343 @@
+use super::{Decoded, MAX_SIG_DIGITS};
+
+/// A floating point value `f * 2^e` with a 64-bit significand.
+#[derive(Clone, Copy)]
+struct Fp {
+    f: u64,
+    e: i32,
+}
+
+impl Fp {
+    /// Multiplies two values, rounding the product's significand to nearest.
+    fn mul(self, other: Self) -> Self {
+        let p = u128::from(self.f) * u128::from(other.f) + (1 << 63);
+
+        Self {
+            f: (p >> 64) as u64,
+            e: self.e + other.e + 64,
+        }
+    }
+
+    /// Shifts the significand so its most significant bit is set.
+    fn normalize(self) -> Self {
+        let shift = self.f.leading_zeros();
+
+        Self {
+            f: self.f << shift,
+            e: self.e - shift as i32,
+        }
+    }
+
+    /// Shifts the significand to have the exponent `e`, which must not be
+    /// greater than the current one.
+    fn normalize_to(self, e: i32) -> Self {
+        let shift = self.e - e;
+        debug_assert!(shift >= 0);
+
+        Self {
+            f: self.f << shift,
+            e,
+        }
+    }
+}
+
+/// The smallest binary exponent of a scaled value.
+const ALPHA: i32 = -60;
+
+/// The largest binary exponent of a scaled value.
+const GAMMA: i32 = -32;
+
+/// Normalized `(f, e, k)` such that `f * 2^e` is `10^k` rounded to nearest.
+///
+/// Covers every `k` from -348 to 340 in steps of 8.
+#[rustfmt::skip]
+static CACHED_POW10: [(u64, i16, i16); 87] = [
+    (0xfa8fd5a0081c0288, -1220, -348),
+    (0xbaaee17fa23ebf76, -1193, -340),
+    (0x8b16fb203055ac76, -1166, -332),
+    (0xcf42894a5dce35ea, -1140, -324),
+    (0x9a6bb0aa55653b2d, -1113, -316),
+    (0xe61acf033d1a45df, -1087, -308),
+    (0xab70fe17c79ac6ca, -1060, -300),
+    (0xff77b1fcbebcdc4f, -1034, -292),
+    (0xbe5691ef416bd60c, -1007, -284),
+    (0x8dd01fad907ffc3c, -980, -276),
+    (0xd3515c2831559a83, -954, -268),
+    (0x9d71ac8fada6c9b5, -927, -260),
+    (0xea9c227723ee8bcb, -901, -252),
+    (0xaecc49914078536d, -874, -244),
+    (0x823c12795db6ce57, -847, -236),
+    (0xc21094364dfb5637, -821, -228),
+    (0x9096ea6f3848984f, -794, -220),
+    (0xd77485cb25823ac7, -768, -212),
+    (0xa086cfcd97bf97f4, -741, -204),
+    (0xef340a98172aace5, -715, -196),
+    (0xb23867fb2a35b28e, -688, -188),
+    (0x84c8d4dfd2c63f3b, -661, -180),
+    (0xc5dd44271ad3cdba, -635, -172),
+    (0x936b9fcebb25c996, -608, -164),
+    (0xdbac6c247d62a584, -582, -156),
+    (0xa3ab66580d5fdaf6, -555, -148),
+    (0xf3e2f893dec3f126, -529, -140),
+    (0xb5b5ada8aaff80b8, -502, -132),
+    (0x87625f056c7c4a8b, -475, -124),
+    (0xc9bcff6034c13053, -449, -116),
+    (0x964e858c91ba2655, -422, -108),
+    (0xdff9772470297ebd, -396, -100),
+    (0xa6dfbd9fb8e5b88f, -369, -92),
+    (0xf8a95fcf88747d94, -343, -84),
+    (0xb94470938fa89bcf, -316, -76),
+    (0x8a08f0f8bf0f156b, -289, -68),
+    (0xcdb02555653131b6, -263, -60),
+    (0x993fe2c6d07b7fac, -236, -52),
+    (0xe45c10c42a2b3b06, -210, -44),
+    (0xaa242499697392d3, -183, -36),
+    (0xfd87b5f28300ca0e, -157, -28),
+    (0xbce5086492111aeb, -130, -20),
+    (0x8cbccc096f5088cc, -103, -12),
+    (0xd1b71758e219652c, -77, -4),
+    (0x9c40000000000000, -50, 4),
+    (0xe8d4a51000000000, -24, 12),
+    (0xad78ebc5ac620000, 3, 20),
+    (0x813f3978f8940984, 30, 28),
+    (0xc097ce7bc90715b3, 56, 36),
+    (0x8f7e32ce7bea5c70, 83, 44),
+    (0xd5d238a4abe98068, 109, 52),
+    (0x9f4f2726179a2245, 136, 60),
+    (0xed63a231d4c4fb27, 162, 68),
+    (0xb0de65388cc8ada8, 189, 76),
+    (0x83c7088e1aab65db, 216, 84),
+    (0xc45d1df942711d9a, 242, 92),
+    (0x924d692ca61be758, 269, 100),
+    (0xda01ee641a708dea, 295, 108),
+    (0xa26da3999aef774a, 322, 116),
+    (0xf209787bb47d6b85, 348, 124),
+    (0xb454e4a179dd1877, 375, 132),
+    (0x865b86925b9bc5c2, 402, 140),
+    (0xc83553c5c8965d3d, 428, 148),
+    (0x952ab45cfa97a0b3, 455, 156),
+    (0xde469fbd99a05fe3, 481, 164),
+    (0xa59bc234db398c25, 508, 172),
+    (0xf6c69a72a3989f5c, 534, 180),
+    (0xb7dcbf5354e9bece, 561, 188),
+    (0x88fcf317f22241e2, 588, 196),
+    (0xcc20ce9bd35c78a5, 614, 204),
+    (0x98165af37b2153df, 641, 212),
+    (0xe2a0b5dc971f303a, 667, 220),
+    (0xa8d9d1535ce3b396, 694, 228),
+    (0xfb9b7cd9a4a7443c, 720, 236),
+    (0xbb764c4ca7a44410, 747, 244),
+    (0x8bab8eefb6409c1a, 774, 252),
+    (0xd01fef10a657842c, 800, 260),
+    (0x9b10a4e5e9913129, 827, 268),
+    (0xe7109bfba19c0c9d, 853, 276),
+    (0xac2820d9623bf429, 880, 284),
+    (0x80444b5e7aa7cf85, 907, 292),
+    (0xbf21e44003acdd2d, 933, 300),
+    (0x8e679c2f5e44ff8f, 960, 308),
+    (0xd433179d9c8cb841, 986, 316),
+    (0x9e19db92b4e31ba9, 1013, 324),
+    (0xeb96bf6ebadf77d9, 1039, 332),
+    (0xaf87023b9bf0ee6b, 1066, 340),
+];
+
+/// Returns a cached `10^k` whose binary exponent is between `alpha` and
+/// `gamma`.
+fn cached_power(alpha: i32, gamma: i32) -> (i32, Fp) {
+    let first = i32::from(CACHED_POW10[0].1);
+    let last = i32::from(CACHED_POW10[CACHED_POW10.len() - 1].1);
+    let range = CACHED_POW10.len() as i32 - 1;
+
+    // The exponents are close to evenly spaced, so interpolate and correct.
+    let mut idx = ((gamma - first) * range / (last - first)).clamp(0, range) as usize;
+    while i32::from(CACHED_POW10[idx].1) > gamma {
+        idx -= 1;
+    }
+    while i32::from(CACHED_POW10[idx].1) < alpha {
+        idx += 1;
+    }
+
+    let (f, e, k) = CACHED_POW10[idx];
+    (i32::from(k), Fp { f, e: i32::from(e) })
+}
+
+/// Returns the largest `kappa` and `10^kappa` with `10^kappa <= x`.
+fn max_pow10_no_more_than(x: u32) -> (u32, u32) {
+    debug_assert!(x > 0);
+
+    let mut kappa = 0;
+    let mut ten_kappa = 1;
+    while ten_kappa <= x / 10 {
+        kappa += 1;
+        ten_kappa *= 10;
+    }
+
+    (kappa, ten_kappa)
+}
+
+/// Writes the shortest digits that round to `d` into `buf` with Loitsch's
+/// Grisu3, if it can.
+///
+/// Gives up when it cannot prove its result is the shortest and closest, in
+/// which case the caller falls back to Dragon4. Returns the number of digits
+/// and `k` such that the value is `0.digits * 10^k`.
+pub(crate) fn format_shortest_opt(d: &Decoded, buf: &mut [u8]) -> Option<(usize, i32)> {
+    debug_assert!(d.mant > 0 && d.minus > 0 && d.plus > 0);
+    debug_assert!(d.mant - d.minus > 0 && d.mant + d.plus < (1 << 61));
+    debug_assert!(buf.len() >= MAX_SIG_DIGITS);
+
+    // Normalize the interval's boundaries and value to a common exponent.
+    let plus = Fp {
+        f: d.mant + d.plus,
+        e: d.exp,
+    }
+    .normalize();
+    let minus = Fp {
+        f: d.mant - d.minus,
+        e: d.exp,
+    }
+    .normalize_to(plus.e);
+    let v = Fp {
+        f: d.mant,
+        e: d.exp,
+    }
+    .normalize_to(plus.e);
+
+    // Scale by a power of ten so the exponent is between `ALPHA` and `GAMMA`.
+    let (minusk, cached) = cached_power(ALPHA - plus.e - 64, GAMMA - plus.e - 64);
+    let plus = plus.mul(cached);
+    let minus = minus.mul(cached);
+    let v = v.mul(cached);
+    debug_assert_eq!(plus.e, minus.e);
+    debug_assert_eq!(plus.e, v.e);
+
+    // Each scaled value may be off by one unit, so shrink the interval by one
+    // unit on each side to stay safe.
+    let plus1 = plus.f + 1;
+    let minus1 = minus.f - 1;
+    let e = plus.e.unsigned_abs();
+    let mask = (1 << e) - 1;
+
+    let plus1int = (plus1 >> e) as u32;
+    let plus1frac = plus1 & mask;
+
+    let (max_kappa, max_ten_kappa) = max_pow10_no_more_than(plus1int);
+    let exp = max_kappa as i32 - minusk + 1;
+
+    let delta1 = plus1 - minus1;
+    let delta1frac = delta1 & mask;
+
+    // Generate digits from the integral part.
+    let mut i = 0;
+    let mut ten_kappa = max_ten_kappa;
+    let mut remainder = plus1int;
+    loop {
+        let q = remainder / ten_kappa;
+        let r = remainder % ten_kappa;
+        buf[i] = b'0' + q as u8;
+        i += 1;
+
+        let plus1rem = (u64::from(r) << e) + plus1frac;
+        if plus1rem < delta1 {
+            let ten_kappa = u64::from(ten_kappa) << e;
+            return round_and_weed(
+                &mut buf[..i],
+                exp,
+                plus1rem,
+                delta1,
+                plus1 - v.f,
+                ten_kappa,
+                1,
+            );
+        }
+
+        if i > max_kappa as usize {
+            break;
+        }
+
+        ten_kappa /= 10;
+        remainder = r;
+    }
+
+    // Generate digits from the fractional part.
+    let mut remainder = plus1frac;
+    let mut threshold = delta1frac;
+    let mut ulp = 1;
+    loop {
+        remainder *= 10;
+        threshold *= 10;
+        ulp *= 10;
+
+        let q = remainder >> e;
+        let r = remainder & mask;
+        buf[i] = b'0' + q as u8;
+        i += 1;
+
+        if r < threshold {
+            return round_and_weed(
+                &mut buf[..i],
+                exp,
+                r,
+                threshold,
+                (plus1 - v.f) * ulp,
+                1 << e,
+                ulp,
+            );
+        }
+
+        remainder = r;
+    }
+}
+
+/// Moves the last digit down toward the scaled value as far as is safe and
+/// checks that the result is unambiguously the closest.
+///
+/// All values are distances from `plus1` in units where `ulp` is the scaled
+/// error and `ten_kappa` is one unit in the last digit.
+fn round_and_weed(
+    buf: &mut [u8],
+    exp: i32,
+    remainder: u64,
+    threshold: u64,
+    plus1v: u64,
+    ten_kappa: u64,
+    ulp: u64,
+) -> Option<(usize, i32)> {
+    // The real value is somewhere between these two.
+    let plus1v_down = plus1v + ulp;
+    let plus1v_up = plus1v - ulp;
+
+    let mut plus1w = remainder;
+    {
+        let last = buf.last_mut()?;
+
+        // Decrement the last digit while the result gets closer to even the
+        // largest possible value.
+        while plus1w < plus1v_up
+            && threshold - plus1w >= ten_kappa
+            && (plus1w + ten_kappa < plus1v_up
+                || plus1v_up - plus1w >= plus1w + ten_kappa - plus1v_up)
+        {
+            *last -= 1;
+            debug_assert!(*last > b'0');
+            plus1w += ten_kappa;
+        }
+    }
+
+    // If the smallest possible value would prefer another decrement, the
+    // closest digits are ambiguous.
+    if plus1w < plus1v_down
+        && threshold - plus1w >= ten_kappa
+        && (plus1w + ten_kappa < plus1v_down
+            || plus1v_down - plus1w >= plus1w + ten_kappa - plus1v_down)
+    {
+        return None;
+    }
+
+    // Make sure the result is safely inside the interval after the error.
+    if 2 * ulp <= plus1w && plus1w <= threshold - 4 * ulp {
+        Some((buf.len(), exp))
+    } else {
+        None
+    }
+}
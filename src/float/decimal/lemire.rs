@@ -0,0 +1,134 @@
+use super::{
+    parse::{BiasedFp, FloatInfo},
+    table::{LARGEST_POWER_OF_FIVE, POWER_OF_FIVE_128, SMALLEST_POWER_OF_FIVE},
+};
+
+/// Converts `w * 10^q` to the closest binary float, if it can, after Eisel &
+/// Lemire's "Number Parsing at a Gigabyte per Second".
+///
+/// Returns a negative exponent if the result cannot be rounded correctly
+/// from 128 bits of `5^q`, in which case the caller must fall back to a
+/// slower algorithm.
+pub(crate) fn compute_float(info: &FloatInfo, q: i64, mut w: u64) -> BiasedFp {
+    let fp_zero = BiasedFp { f: 0, e: 0 };
+    let fp_inf = BiasedFp {
+        f: 0,
+        e: info.infinite_power,
+    };
+    let fp_error = BiasedFp { f: 0, e: -1 };
+
+    // The value can only be zero or infinity.
+    if w == 0 || q < i64::from(info.smallest_power_of_ten) {
+        return fp_zero;
+    } else if q > i64::from(info.largest_power_of_ten) {
+        return fp_inf;
+    }
+
+    // Normalize so the most significant bit is set.
+    let lz = w.leading_zeros();
+    w <<= lz;
+
+    let (lo, hi) = compute_product_approx(q, w, info.sig_bits + 3);
+    if lo == u64::MAX {
+        // The approximation of `w * 5^q` may be off by one, which can only
+        // change the rounding if `q` is small enough for it to be exact.
+        if !(-27..=55).contains(&q) {
+            return fp_error;
+        }
+    }
+
+    let upperbit = (hi >> 63) as i32;
+    let shift = upperbit + 64 - info.sig_bits as i32 - 3;
+    let mut mantissa = hi >> shift;
+    let mut power2 = power(q as i32) + upperbit - lz as i32 + info.bias;
+
+    if power2 <= 0 {
+        // More than 64 bits below the smallest exponent, so zero.
+        if -power2 + 1 >= 64 {
+            return fp_zero;
+        }
+
+        // Subnormal, possibly rounding up to the smallest normal.
+        mantissa >>= -power2 + 1;
+        mantissa += mantissa & 1;
+        mantissa >>= 1;
+        power2 = i32::from(mantissa >= (1 << info.sig_bits));
+
+        return BiasedFp {
+            f: mantissa & ((1 << info.sig_bits) - 1),
+            e: power2,
+        };
+    }
+
+    // A product exactly halfway between two floats must round to even rather
+    // than up, which can only happen when it is exact.
+    if lo <= 1
+        && q >= i64::from(info.min_exponent_round_to_even)
+        && q <= i64::from(info.max_exponent_round_to_even)
+        && mantissa & 3 == 1
+        && (mantissa << shift) == hi
+    {
+        mantissa &= !1;
+    }
+
+    // Round to nearest, then shift the significant bits into place.
+    mantissa += mantissa & 1;
+    mantissa >>= 1;
+    if mantissa >= (2 << info.sig_bits) {
+        // Rounding carried into a new bit.
+        mantissa = 1 << info.sig_bits;
+        power2 += 1;
+    }
+
+    // Clear the implicit bit.
+    mantissa &= !(1 << info.sig_bits);
+
+    if power2 >= info.infinite_power {
+        return fp_inf;
+    }
+
+    BiasedFp {
+        f: mantissa,
+        e: power2,
+    }
+}
+
+/// Returns `floor(q * log2(10)) + 63`.
+///
+/// `217706 / 2^16` approximates `log2(10)` closely enough for every decimal
+/// exponent of a finite float.
+fn power(q: i32) -> i32 {
+    (q.wrapping_mul(152_170 + 65536) >> 16) + 63
+}
+
+/// Returns the 128-bit product of `a` and `b` as `(low, high)` halves.
+fn full_multiplication(a: u64, b: u64) -> (u64, u64) {
+    let r = u128::from(a) * u128::from(b);
+    (r as u64, (r >> 64) as u64)
+}
+
+/// Approximates the top 128 bits of `w * 5^q` as `(low, high)` halves, only
+/// refining the low half when the top `precision` bits may be affected.
+fn compute_product_approx(q: i64, w: u64, precision: u32) -> (u64, u64) {
+    debug_assert!(q >= i64::from(SMALLEST_POWER_OF_FIVE));
+    debug_assert!(q <= i64::from(LARGEST_POWER_OF_FIVE));
+    debug_assert!(precision <= 64);
+
+    let mask = u64::MAX.checked_shr(precision).unwrap_or(u64::MAX);
+
+    let index = (q - i64::from(SMALLEST_POWER_OF_FIVE)) as usize;
+    let (hi5, lo5) = POWER_OF_FIVE_128[index];
+
+    let (mut first_lo, mut first_hi) = full_multiplication(w, hi5);
+    if first_hi & mask == mask {
+        // The bits below the needed precision are all ones, so a carry from
+        // the low half of `5^q` could change them.
+        let (_, second_hi) = full_multiplication(w, lo5);
+        first_lo = first_lo.wrapping_add(second_hi);
+        if second_hi > first_lo {
+            first_hi += 1;
+        }
+    }
+
+    (first_lo, first_hi)
+}
@@ -0,0 +1,289 @@
+use crate::{Float, Num, ParseNumError};
+
+use super::{
+    lemire::compute_float,
+    slow::{parse_long_mantissa, Decimal},
+    table::SMALLEST_POWER_OF_FIVE,
+};
+
+/// A float's magnitude as its raw significand bits and biased exponent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BiasedFp {
+    /// The significand without the implicit bit.
+    pub(crate) f: u64,
+
+    /// The biased exponent, or negative if the conversion failed.
+    pub(crate) e: i32,
+}
+
+/// Parameters of a float type's binary format used by the conversions.
+pub(crate) struct FloatInfo {
+    /// The number of explicitly stored significand bits.
+    pub(crate) sig_bits: u32,
+
+    /// The exponent bias.
+    pub(crate) bias: i32,
+
+    /// The biased exponent of infinity.
+    pub(crate) infinite_power: i32,
+
+    /// Any `w * 10^q` with `q` below this is zero.
+    pub(crate) smallest_power_of_ten: i32,
+
+    /// Any non-zero `w * 10^q` with `q` above this is infinite.
+    pub(crate) largest_power_of_ten: i32,
+
+    /// The range of `q` where `w * 5^q` can be exactly halfway between floats.
+    pub(crate) min_exponent_round_to_even: i32,
+    pub(crate) max_exponent_round_to_even: i32,
+
+    /// The largest power of ten that is exact in the float type.
+    max_exponent_fast_path: i64,
+
+    /// The largest significand that is exact in the float type.
+    max_mantissa_fast_path: u64,
+}
+
+impl FloatInfo {
+    pub(crate) fn of<F: Float>() -> Self {
+        let digits = F::MANTISSA_DIGITS;
+
+        // `floor(x * log10(2))`, see `dragon::estimate_scaling_factor`.
+        let smallest = ((i64::from(F::MIN_EXP - digits as i32 - 65) * 1_292_913_986) >> 32) + 1;
+
+        Self {
+            sig_bits: F::MANTISSA_BITS,
+            bias: F::EXPONENT_BIAS,
+            infinite_power: (1 << F::EXPONENT_BITS) - 1,
+            smallest_power_of_ten: (smallest as i32).max(SMALLEST_POWER_OF_FIVE),
+            largest_power_of_ten: F::MAX_10_EXP,
+            min_exponent_round_to_even: -log5_floor(64 - digits),
+            max_exponent_round_to_even: log5_floor(digits + 1),
+            max_exponent_fast_path: i64::from(log5_floor(digits)),
+            max_mantissa_fast_path: 1 << digits,
+        }
+    }
+}
+
+/// Returns `floor(log5(2^bits))`.
+fn log5_floor(bits: u32) -> i32 {
+    let mut e = 0;
+    let mut p = 5_u128;
+    while p <= 1 << bits {
+        p *= 5;
+        e += 1;
+    }
+
+    e
+}
+
+/// Returns the `idx`th byte of `s` if it is an ASCII digit.
+fn digit_at(s: &[u8], idx: usize) -> Option<u8> {
+    s.get(idx).copied().filter(u8::is_ascii_digit)
+}
+
+/// Returns the end of the run of digits starting at `start`.
+///
+/// A separator is only part of the run if it is between two digits.
+fn scan_digits(s: &[u8], start: usize, separator: Option<u8>) -> usize {
+    let mut i = start;
+
+    while let Some(&c) = s.get(i) {
+        let is_separator = Some(c) == separator
+            && i > start
+            && digit_at(s, i - 1).is_some()
+            && digit_at(s, i + 1).is_some();
+
+        if c.is_ascii_digit() || is_separator {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+
+    i
+}
+
+/// Parses `inf`, `infinity` or `nan` in any case, returning the number of
+/// bytes they take up.
+fn parse_special<F: Float>(s: &[u8]) -> Option<(F, usize)> {
+    let starts_with =
+        |prefix: &[u8]| s.len() >= prefix.len() && s[..prefix.len()].eq_ignore_ascii_case(prefix);
+
+    if starts_with(b"infinity") {
+        Some((F::INFINITY, 8))
+    } else if starts_with(b"inf") {
+        Some((F::INFINITY, 3))
+    } else if starts_with(b"nan") {
+        Some((F::NAN, 3))
+    } else {
+        None
+    }
+}
+
+/// Returns `10^e` exactly, where `e` is no greater than
+/// `FloatInfo::max_exponent_fast_path`.
+fn pow10<F: Float>(e: i64) -> F {
+    10_u128.pow(e as u32).cast()
+}
+
+/// Parses a decimal float from the start of `s`, returning it and the number
+/// of bytes it takes up.
+pub(crate) fn parse_prefix<F: Float>(
+    s: &[u8],
+    separator: Option<u8>,
+) -> Result<(F, usize), ParseNumError> {
+    if s.is_empty() {
+        return Err(ParseNumError::Empty);
+    }
+
+    let (negative, start) = match s[0] {
+        b'-' => (true, 1),
+        b'+' => (false, 1),
+        _ => (false, 0),
+    };
+    let apply_sign = |v: F| if negative { -v } else { v };
+
+    if let Some((v, len)) = parse_special::<F>(&s[start..]) {
+        return Ok((apply_sign(v), start + len));
+    }
+
+    let int_end = scan_digits(s, start, separator);
+    let (frac_start, frac_end) = if s.get(int_end) == Some(&b'.') {
+        (int_end + 1, scan_digits(s, int_end + 1, separator))
+    } else {
+        (int_end, int_end)
+    };
+
+    if int_end == start && frac_end == frac_start {
+        return Err(ParseNumError::InvalidFloat);
+    }
+
+    // The exponent is only part of the float if it has digits.
+    let mut end = frac_end;
+    let mut exp = 0_i64;
+    if matches!(s.get(end), Some(b'e' | b'E')) {
+        let (exp_negative, exp_start) = match s.get(end + 1) {
+            Some(b'-') => (true, end + 2),
+            Some(b'+') => (false, end + 2),
+            _ => (false, end + 1),
+        };
+        let exp_end = scan_digits(s, exp_start, separator);
+
+        if exp_end > exp_start {
+            for &c in s[exp_start..exp_end].iter().filter(|c| c.is_ascii_digit()) {
+                // Anything this large is zero or infinite, so stop counting.
+                if exp < 0x10000 {
+                    exp = exp * 10 + i64::from(c - b'0');
+                }
+            }
+            if exp_negative {
+                exp = -exp;
+            }
+            end = exp_end;
+        }
+    }
+
+    // Each digit and whether it is after the point.
+    let digits = || {
+        let int = s[start..int_end].iter().map(|&c| (c, false));
+        let frac = s[frac_start..frac_end].iter().map(|&c| (c, true));
+        int.chain(frac)
+            .filter(|(c, _)| c.is_ascii_digit())
+            .map(|(c, frac)| (c - b'0', frac))
+    };
+
+    // Take the first 19 significant digits, which always fit in a `u64`.
+    let mut mantissa = 0_u64;
+    let mut num_digits = 0;
+    let mut exponent = exp;
+    let mut many_digits = false;
+    for (d, frac) in digits() {
+        if num_digits == 0 && d == 0 {
+            exponent -= i64::from(frac);
+        } else if num_digits < 19 {
+            mantissa = mantissa * 10 + u64::from(d);
+            num_digits += 1;
+            exponent -= i64::from(frac);
+        } else {
+            exponent += i64::from(!frac);
+            many_digits |= d != 0;
+        }
+    }
+
+    let info = FloatInfo::of::<F>();
+
+    if let Some(v) = try_fast_path::<F>(&info, mantissa, exponent, many_digits) {
+        return Ok((apply_sign(v), end));
+    }
+
+    let mut fp = compute_float(&info, exponent, mantissa);
+
+    // With digits dropped the value is somewhere between `mantissa` and
+    // `mantissa + 1`, so those must round to the same float.
+    if many_digits && fp.e >= 0 && fp != compute_float(&info, exponent, mantissa + 1) {
+        fp.e = -1;
+    }
+
+    if fp.e < 0 {
+        let mut d = Decimal::new();
+        let mut decimal_point = exp;
+        let mut trailing_zeros = 0;
+        let mut seen_non_zero = false;
+
+        for (digit, frac) in digits() {
+            if !seen_non_zero && digit == 0 {
+                decimal_point -= i64::from(frac);
+                continue;
+            }
+
+            seen_non_zero = true;
+            d.push_digit(digit);
+            decimal_point += i64::from(!frac);
+            trailing_zeros = if digit == 0 { trailing_zeros + 1 } else { 0 };
+        }
+
+        d.finish(trailing_zeros, decimal_point);
+        fp = parse_long_mantissa(&info, d);
+    }
+
+    let bits = fp.f | ((fp.e as u64) << info.sig_bits);
+
+    Ok((apply_sign(F::from_bits(F::Bits::cast_from(bits))), end))
+}
+
+/// Converts `mantissa * 10^exponent` with a single correctly rounded float
+/// operation, if both operands are exact.
+fn try_fast_path<F: Float>(
+    info: &FloatInfo,
+    mantissa: u64,
+    exponent: i64,
+    many_digits: bool,
+) -> Option<F> {
+    let max_exp = info.max_exponent_fast_path;
+
+    if many_digits || mantissa > info.max_mantissa_fast_path {
+        return None;
+    }
+
+    if (-max_exp..=max_exp).contains(&exponent) {
+        let v = mantissa.cast::<F>();
+
+        if exponent < 0 {
+            Some(v / pow10::<F>(-exponent))
+        } else {
+            Some(v * pow10::<F>(exponent))
+        }
+    } else if exponent > max_exp && exponent - max_exp <= 19 {
+        // Move some of a large exponent into the mantissa if it stays exact.
+        let mantissa = mantissa.checked_mul(10_u64.pow((exponent - max_exp) as u32))?;
+
+        if mantissa > info.max_mantissa_fast_path {
+            return None;
+        }
+
+        Some(mantissa.cast::<F>() * pow10::<F>(max_exp))
+    } else {
+        None
+    }
+}
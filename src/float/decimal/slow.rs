@@ -0,0 +1,313 @@
+use super::parse::{BiasedFp, FloatInfo};
+
+/// A decimal number `0.digits * 10^decimal_point` with a bounded number of
+/// digits.
+pub(crate) struct Decimal {
+    /// The number of significant digits, which may be more than `MAX_DIGITS`.
+    num_digits: usize,
+
+    /// The position of the decimal point relative to the first digit.
+    decimal_point: i32,
+
+    /// Whether non-zero digits were dropped beyond `MAX_DIGITS`.
+    truncated: bool,
+
+    /// The digits, as values from 0 to 9 rather than ASCII.
+    digits: [u8; Self::MAX_DIGITS],
+}
+
+impl Decimal {
+    /// Enough digits to round every float correctly: halfway between two
+    /// `f64` values needs at most 767 significant digits.
+    const MAX_DIGITS: usize = 768;
+
+    /// Beyond this the value is certainly zero or infinite.
+    const DECIMAL_POINT_RANGE: i32 = 2047;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            num_digits: 0,
+            decimal_point: 0,
+            truncated: false,
+            digits: [0; Self::MAX_DIGITS],
+        }
+    }
+
+    /// Appends a digit, or records that there was one beyond `MAX_DIGITS`.
+    pub(crate) fn push_digit(&mut self, digit: u8) {
+        if self.num_digits < Self::MAX_DIGITS {
+            self.digits[self.num_digits] = digit;
+        } else if digit != 0 {
+            self.truncated = true;
+        }
+        self.num_digits += 1;
+    }
+
+    /// Finishes building a decimal from digits given by `push_digit`.
+    ///
+    /// `trailing_zeros` is the number of zeros at the end of the digits and
+    /// `decimal_point` is where the point is relative to the first digit.
+    pub(crate) fn finish(&mut self, trailing_zeros: usize, decimal_point: i64) {
+        self.num_digits -= trailing_zeros;
+        self.num_digits = self.num_digits.min(Self::MAX_DIGITS);
+        self.decimal_point = decimal_point.clamp(-0x10000, 0x10000) as i32;
+    }
+
+    /// Removes trailing zeros.
+    fn trim(&mut self) {
+        while self.num_digits != 0 && self.digits[self.num_digits - 1] == 0 {
+            self.num_digits -= 1;
+        }
+    }
+
+    /// Returns the integral part, rounded to nearest with ties to even.
+    fn round(&self) -> u64 {
+        if self.num_digits == 0 || self.decimal_point < 0 {
+            return 0;
+        } else if self.decimal_point > 18 {
+            return u64::MAX;
+        }
+
+        let dp = self.decimal_point as usize;
+        let mut n = 0_u64;
+        for i in 0..dp {
+            n *= 10;
+            if i < self.num_digits {
+                n += u64::from(self.digits[i]);
+            }
+        }
+
+        let mut round_up = false;
+        if dp < self.num_digits {
+            round_up = self.digits[dp] >= 5;
+            if self.digits[dp] == 5 && dp + 1 == self.num_digits {
+                round_up = self.truncated || (dp != 0 && self.digits[dp - 1] & 1 != 0);
+            }
+        }
+
+        n + u64::from(round_up)
+    }
+
+    /// Multiplies by `2^shift`, where `shift` is at most 60.
+    fn left_shift(&mut self, shift: u32) {
+        if self.num_digits == 0 {
+            return;
+        }
+
+        let num_new_digits = self.number_of_new_digits(shift);
+        let mut read_index = self.num_digits;
+        let mut write_index = self.num_digits + num_new_digits;
+        let mut n = 0_u64;
+
+        while read_index != 0 {
+            read_index -= 1;
+            write_index -= 1;
+            n += u64::from(self.digits[read_index]) << shift;
+            self.write_digit(write_index, (n % 10) as u8);
+            n /= 10;
+        }
+
+        while n > 0 {
+            write_index -= 1;
+            self.write_digit(write_index, (n % 10) as u8);
+            n /= 10;
+        }
+
+        self.num_digits = (self.num_digits + num_new_digits).min(Self::MAX_DIGITS);
+        self.decimal_point += num_new_digits as i32;
+        self.trim();
+    }
+
+    /// Writes a digit at `index` if it fits, otherwise records truncation.
+    fn write_digit(&mut self, index: usize, digit: u8) {
+        if index < Self::MAX_DIGITS {
+            self.digits[index] = digit;
+        } else if digit > 0 {
+            self.truncated = true;
+        }
+    }
+
+    /// Returns the number of integral digits gained by multiplying by
+    /// `2^shift`.
+    fn number_of_new_digits(&self, shift: u32) -> usize {
+        // Multiplying by `2^shift` is multiplying by `10^shift` and dividing
+        // by `5^shift`, which gains one digit fewer when the digits are less
+        // than those of `5^shift`.
+        let mut pow5 = [0_u8; 43];
+        let mut len = 1;
+        pow5[0] = 1;
+        for _ in 0..shift {
+            let mut carry = 0;
+            for d in pow5[..len].iter_mut().rev() {
+                let v = *d * 5 + carry;
+                *d = v % 10;
+                carry = v / 10;
+            }
+            if carry > 0 {
+                pow5.copy_within(..len, 1);
+                pow5[0] = carry;
+                len += 1;
+            }
+        }
+
+        let new_digits = shift as usize + 1 - len;
+        for (i, &p) in pow5[..len].iter().enumerate() {
+            if i >= self.num_digits || self.digits[i] < p {
+                return new_digits - 1;
+            } else if self.digits[i] > p {
+                return new_digits;
+            }
+        }
+
+        new_digits
+    }
+
+    /// Divides by `2^shift`, where `shift` is at most 60.
+    fn right_shift(&mut self, shift: u32) {
+        let mut read_index = 0;
+        let mut write_index = 0;
+        let mut n = 0_u64;
+
+        while (n >> shift) == 0 {
+            if read_index < self.num_digits {
+                n = 10 * n + u64::from(self.digits[read_index]);
+                read_index += 1;
+            } else if n == 0 {
+                return;
+            } else {
+                while (n >> shift) == 0 {
+                    n *= 10;
+                    read_index += 1;
+                }
+                break;
+            }
+        }
+
+        self.decimal_point -= read_index as i32 - 1;
+        if self.decimal_point < -Self::DECIMAL_POINT_RANGE {
+            self.num_digits = 0;
+            self.decimal_point = 0;
+            self.truncated = false;
+            return;
+        }
+
+        let mask = (1_u64 << shift) - 1;
+        while read_index < self.num_digits {
+            let new_digit = (n >> shift) as u8;
+            n = 10 * (n & mask) + u64::from(self.digits[read_index]);
+            read_index += 1;
+            self.digits[write_index] = new_digit;
+            write_index += 1;
+        }
+
+        while n > 0 {
+            let new_digit = (n >> shift) as u8;
+            n = 10 * (n & mask);
+            if write_index < Self::MAX_DIGITS {
+                self.digits[write_index] = new_digit;
+                write_index += 1;
+            } else if new_digit > 0 {
+                self.truncated = true;
+            }
+        }
+
+        self.num_digits = write_index;
+        self.trim();
+    }
+}
+
+/// Converts a decimal to the closest binary float, after Nigel Tao's "Simple
+/// Decimal Conversion" from Wuffs.
+///
+/// Always correct but slower than Eisel-Lemire, which falls back to this.
+pub(crate) fn parse_long_mantissa(info: &FloatInfo, mut d: Decimal) -> BiasedFp {
+    const MAX_SHIFT: u32 = 60;
+
+    /// The largest shift that moves the decimal point by no more than the
+    /// number of digits before it.
+    const POWERS: [u8; 19] = [
+        0, 3, 6, 9, 13, 16, 19, 23, 26, 29, 33, 36, 39, 43, 46, 49, 53, 56, 59,
+    ];
+
+    let get_shift = |n: usize| POWERS.get(n).map_or(MAX_SHIFT, |&s| u32::from(s));
+
+    let fp_zero = BiasedFp { f: 0, e: 0 };
+    let fp_inf = BiasedFp {
+        f: 0,
+        e: info.infinite_power,
+    };
+
+    // The value can only be zero or infinity.
+    if d.num_digits == 0 || d.decimal_point < -324 {
+        return fp_zero;
+    } else if d.decimal_point >= 310 {
+        return fp_inf;
+    }
+
+    let mut exp2 = 0_i32;
+
+    // Shift right toward the range from 1/2 to 1.
+    while d.decimal_point > 0 {
+        let shift = get_shift(d.decimal_point as usize);
+        d.right_shift(shift);
+        if d.decimal_point < -Decimal::DECIMAL_POINT_RANGE {
+            return fp_zero;
+        }
+        exp2 += shift as i32;
+    }
+
+    // Shift left toward the range from 1/2 to 1.
+    while d.decimal_point <= 0 {
+        let shift = if d.decimal_point == 0 {
+            match d.digits[0] {
+                5.. => break,
+                0 | 1 => 2,
+                _ => 1,
+            }
+        } else {
+            get_shift(d.decimal_point.unsigned_abs() as usize)
+        };
+        d.left_shift(shift);
+        if d.decimal_point > Decimal::DECIMAL_POINT_RANGE {
+            return fp_inf;
+        }
+        exp2 -= shift as i32;
+    }
+
+    // The binary format's significand is from 1 to 2, not 1/2 to 1.
+    exp2 -= 1;
+
+    let min_exp = -info.bias;
+    while min_exp + 1 > exp2 {
+        let n = ((min_exp + 1 - exp2) as u32).min(MAX_SHIFT);
+        d.right_shift(n);
+        exp2 += n as i32;
+    }
+
+    if exp2 - min_exp >= info.infinite_power {
+        return fp_inf;
+    }
+
+    // Shift the implicit bit into the integral part and round.
+    d.left_shift(info.sig_bits + 1);
+    let mut mantissa = d.round();
+    if mantissa >= (1 << (info.sig_bits + 1)) {
+        // Rounding carried into a new bit.
+        d.right_shift(1);
+        exp2 += 1;
+        mantissa = d.round();
+        if exp2 - min_exp >= info.infinite_power {
+            return fp_inf;
+        }
+    }
+
+    let mut power2 = exp2 - min_exp;
+    if mantissa < (1 << info.sig_bits) {
+        power2 -= 1;
+    }
+
+    BiasedFp {
+        f: mantissa & ((1 << info.sig_bits) - 1),
+        e: power2,
+    }
+}
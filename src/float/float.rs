@@ -1,6 +1,10 @@
 use std::{cmp::Ordering, num::FpCategory};
 
-use crate::{FloatConst, Num, Signed, UnsignedInt};
+use crate::{
+    float::{decimal::parse_prefix, hex::parse_hex_prefix, special},
+    FloatBuf, FloatConst, FloatDisplay, FloatFormat, HexFloatBuf, Int, MinMax, Num, ParseNumError,
+    RoundingMode, Signed, Tolerance, UnsignedInt,
+};
 
 /// Flating point types.
 ///
@@ -24,7 +28,7 @@ pub trait Float: Num + FloatConst + Signed {
     ///
     /// This is not normally defined for floats and is only here for the
     /// to/from endian functons.
-    type Bytes;
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy;
 
     /// The radix or base of the internal representation.
     const RADIX: u32;
@@ -62,6 +66,24 @@ pub trait Float: Num + FloatConst + Signed {
     /// Negative infinity (−∞).
     const NEG_INFINITY: Self;
 
+    /// The largest integer such that it and every integer below it down to
+    /// zero are exactly representable, `2^MANTISSA_DIGITS`.
+    const MAX_SAFE_INT: Self;
+
+    /// The negative of `MAX_SAFE_INT`, `-2^MANTISSA_DIGITS`.
+    const MIN_SAFE_INT: Self;
+
+    /// Number of bits in the biased exponent field.
+    const EXPONENT_BITS: u32;
+
+    /// Number of bits in the mantissa field, `MANTISSA_DIGITS - 1` as the
+    /// leading bit is implicit.
+    const MANTISSA_BITS: u32;
+
+    /// The value subtracted from the biased exponent field to get the power
+    /// of 2 of a normal value, `MAX_EXP - 1`.
+    const EXPONENT_BIAS: i32;
+
     /// Returns the largest integer less than or equal to `self`.
     #[must_use]
     fn floor(self) -> Self;
@@ -320,6 +342,364 @@ pub trait Float: Num + FloatConst + Signed {
     /// Returns a number composed of the magnitude of `self` and the sign of `sign`.
     #[must_use]
     fn copysign(self, sign: Self) -> Self;
+
+    /// Rounds `self` to an integer using the given rounding mode.
+    #[must_use]
+    #[inline]
+    fn round_with(self, mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::Floor => self.floor(),
+            RoundingMode::Ceil => self.ceil(),
+            RoundingMode::NearestAway => self.round(),
+            RoundingMode::NearestEven => self.round_ties_even(),
+            RoundingMode::TowardZero => self.trunc(),
+        }
+    }
+
+    /// Rounds `self` using the given rounding mode and converts it to the integer type `I`.
+    ///
+    /// Returns `None` if `self` is NaN or the rounded value is outside `I`'s range.
+    #[must_use]
+    #[inline]
+    fn checked_to_int<I: Int>(self, mode: RoundingMode) -> Option<I> {
+        let r = self.round_with(mode);
+
+        // `I::MIN` is either zero or a negative power of two, both exact in `Self`.
+        let min = I::MIN.cast::<Self>();
+        let end = if min < Self::ZERO {
+            -min
+        } else {
+            (I::MAX / I::TWO + I::ONE).cast::<Self>() * Self::TWO
+        };
+
+        if r >= min && r < end {
+            Some(r.cast())
+        } else {
+            None
+        }
+    }
+
+    /// Rounds `self` using the given rounding mode and converts it to the integer type `I`.
+    ///
+    /// Values outside `I`'s range are clamped to `I::MIN` or `I::MAX` and NaN
+    /// becomes zero, the same as an `as` cast.
+    #[must_use]
+    #[inline]
+    fn saturating_to_int<I: Int>(self, mode: RoundingMode) -> I {
+        self.round_with(mode).cast()
+    }
+
+    /// Converts `self` to the integer type `I` without rounding.
+    ///
+    /// Returns `None` if `self` has a fractional part, is NaN or is outside
+    /// `I`'s range. The inverse of `from_int_exact`.
+    #[must_use]
+    #[inline]
+    fn to_int_exact<I: Int>(self) -> Option<I> {
+        if self.fract() != Self::ZERO {
+            return None;
+        }
+
+        self.checked_to_int(RoundingMode::TowardZero)
+    }
+
+    /// Converts an integer to `Self` without rounding.
+    ///
+    /// Returns `None` if `i` cannot be represented exactly, which can only
+    /// happen when its magnitude is greater than `MAX_SAFE_INT`.
+    #[must_use]
+    #[inline]
+    fn from_int_exact<I: Int>(i: I) -> Option<Self> {
+        let f = i.cast::<Self>();
+
+        if f.to_int_exact::<I>() == Some(i) {
+            Some(f)
+        } else {
+            None
+        }
+    }
+
+    /// Splits `self` into its IEEE 754 fields: the sign bit, the biased
+    /// exponent and the mantissa without the implicit leading bit.
+    ///
+    /// The fields are right-aligned, so the exponent is less than
+    /// `2^EXPONENT_BITS` and the mantissa less than `2^MANTISSA_BITS`.
+    #[must_use]
+    #[inline]
+    fn decompose(self) -> (Self::Bits, Self::Bits, Self::Bits) {
+        let bits = self.to_bits();
+        let one = Self::Bits::ONE;
+
+        let sign = bits >> (Self::Bits::BITS - 1);
+        let exponent = (bits >> Self::MANTISSA_BITS) & ((one << Self::EXPONENT_BITS) - one);
+        let mantissa = bits & ((one << Self::MANTISSA_BITS) - one);
+
+        (sign, exponent, mantissa)
+    }
+
+    /// Builds a value from its IEEE 754 fields, the inverse of `decompose`.
+    ///
+    /// Returns `None` if `sign` is not 0 or 1, `exponent` does not fit in
+    /// `EXPONENT_BITS` bits or `mantissa` does not fit in `MANTISSA_BITS` bits.
+    #[must_use]
+    #[inline]
+    fn compose(sign: Self::Bits, exponent: Self::Bits, mantissa: Self::Bits) -> Option<Self> {
+        if sign > Self::Bits::ONE
+            || exponent >> Self::EXPONENT_BITS != Self::Bits::ZERO
+            || mantissa >> Self::MANTISSA_BITS != Self::Bits::ZERO
+        {
+            return None;
+        }
+
+        let bits = (sign << (Self::Bits::BITS - 1)) | (exponent << Self::MANTISSA_BITS) | mantissa;
+
+        Some(Self::from_bits(bits))
+    }
+
+    /// Returns the number of representable values from `self` to `other`, so
+    /// adjacent values are 1 apart.
+    ///
+    /// Positive and negative zero are 0 apart, and values either side of
+    /// zero are as far apart as the sum of their distances from it. Returns
+    /// `Self::Bits::MAX` if either value is NaN.
+    #[must_use]
+    #[inline]
+    fn ulps_between(self, other: Self) -> Self::Bits {
+        if self.is_nan() || other.is_nan() {
+            return Self::Bits::MAX;
+        }
+
+        // Map sign and magnitude onto a line with zero in the middle.
+        let ordered = |v: Self| {
+            let (sign, _, _) = v.decompose();
+            let middle = Self::Bits::ONE << (Self::Bits::BITS - 1);
+            let magnitude = Signed::abs(v).to_bits();
+
+            if sign == Self::Bits::ZERO {
+                middle + magnitude
+            } else {
+                middle - magnitude
+            }
+        };
+
+        let (a, b) = (ordered(self), ordered(other));
+        if a > b {
+            a - b
+        } else {
+            b - a
+        }
+    }
+
+    /// Returns whether `self` and `other` are equal within any of the given
+    /// tolerances.
+    ///
+    /// NaN is never equal to anything, and infinities are only equal to
+    /// themselves.
+    #[must_use]
+    #[inline]
+    fn approx_eq(self, other: Self, tolerance: Tolerance<Self>) -> bool {
+        tolerance.accepts(self, other)
+    }
+
+    /// Formats `self` as a decimal string into a stack buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the format's precision is greater than `FloatBuf::MAX_PRECISION`.
+    #[must_use]
+    #[inline]
+    fn to_decimal(self, format: FloatFormat) -> FloatBuf {
+        FloatBuf::new(self, format)
+    }
+
+    /// Formats `self` as a decimal string into the start of `buf`.
+    ///
+    /// Returns the number of bytes written, or `None` if `buf` is too small,
+    /// in which case its contents are unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the format's precision is greater than `FloatBuf::MAX_PRECISION`.
+    #[inline]
+    fn write_decimal(self, buf: &mut [u8], format: FloatFormat) -> Option<usize> {
+        let s = FloatBuf::new(self, format);
+        let s = s.as_bytes();
+        buf.get_mut(..s.len())?.copy_from_slice(s);
+
+        Some(s.len())
+    }
+
+    /// Parses a decimal float from the start of `bytes`, returning it and the
+    /// number of bytes it takes up.
+    ///
+    /// Accepts an optional sign followed by either `inf`, `infinity` or `nan`
+    /// in any case, or digits with an optional decimal point and exponent such
+    /// as `-12.5e-3`. The result is correctly rounded to nearest, ties to even.
+    ///
+    /// If `separator` is given, that byte may appear between any two digits
+    /// and is ignored, such as `_` in `1_000.5`. It must not be a digit, sign,
+    /// point or exponent character.
+    ///
+    /// Parsing stops at the first byte that cannot continue the float, so the
+    /// rest of `bytes` may hold anything.
+    fn from_decimal_prefix(
+        bytes: &[u8],
+        separator: Option<u8>,
+    ) -> Result<(Self, usize), ParseNumError> {
+        parse_prefix(bytes, separator)
+    }
+
+    /// Parses a decimal float from the whole of `bytes`.
+    ///
+    /// See `from_decimal_prefix` for the accepted syntax. Fails with
+    /// `ParseNumError::InvalidFloat` if anything follows the float.
+    fn from_decimal(bytes: &[u8], separator: Option<u8>) -> Result<Self, ParseNumError> {
+        match parse_prefix(bytes, separator)? {
+            (v, len) if len == bytes.len() => Ok(v),
+            _ => Err(ParseNumError::InvalidFloat),
+        }
+    }
+
+    /// Formats `self` as a hexadecimal float literal into a stack buffer.
+    ///
+    /// Normal values are written as `0x1.8p+3`, subnormals as `0x0.8p-1022`
+    /// with the smallest normal exponent, and zero as `0x0p+0`, all with
+    /// trailing zeros trimmed. Infinities are written as `inf`, the default
+    /// quiet NaN as `nan` and any other NaN as its fraction bits, such as
+    /// `nan(0x1)`. Negative values, including NaNs, are prefixed with `-`.
+    ///
+    /// The output can be parsed back by `from_hex` into the same bits.
+    #[must_use]
+    #[inline]
+    fn to_hex(self) -> HexFloatBuf {
+        HexFloatBuf::new(self)
+    }
+
+    /// Formats `self` as a hexadecimal float literal into the start of `buf`.
+    ///
+    /// Returns the number of bytes written, or `None` if `buf` is too small,
+    /// in which case its contents are unspecified.
+    #[inline]
+    fn write_hex(self, buf: &mut [u8]) -> Option<usize> {
+        let s = HexFloatBuf::new(self);
+        let s = s.as_bytes();
+        buf.get_mut(..s.len())?.copy_from_slice(s);
+
+        Some(s.len())
+    }
+
+    /// Parses a hexadecimal float literal from the start of `bytes`, returning
+    /// it and the number of bytes it takes up.
+    ///
+    /// Accepts an optional sign followed by either `inf`, `infinity`, `nan` or
+    /// `nan(0x...)` in any case, or `0x` with hexadecimal digits, an optional
+    /// point and an optional binary exponent such as `0x1.8p+3`. Inexact
+    /// values are rounded to nearest, ties to even.
+    ///
+    /// A NaN payload gives its fraction bits. If it is malformed, zero or
+    /// does not fit, only the `nan` is parsed, giving the default quiet NaN.
+    fn from_hex_prefix(bytes: &[u8]) -> Result<(Self, usize), ParseNumError> {
+        parse_hex_prefix(bytes)
+    }
+
+    /// Parses a hexadecimal float literal from the whole of `bytes`.
+    ///
+    /// See `from_hex_prefix` for the accepted syntax. Fails with
+    /// `ParseNumError::InvalidFloat` if anything follows the float.
+    fn from_hex(bytes: &[u8]) -> Result<Self, ParseNumError> {
+        match parse_hex_prefix(bytes)? {
+            (v, len) if len == bytes.len() => Ok(v),
+            _ => Err(ParseNumError::InvalidFloat),
+        }
+    }
+
+    /// Returns an adapter that formats `self` as a decimal string with
+    /// `write!` and the other `core::fmt` macros.
+    #[must_use]
+    #[inline(always)]
+    fn display_decimal(self, format: FloatFormat) -> FloatDisplay<Self> {
+        FloatDisplay::new(self, format)
+    }
+
+    /// Returns the gamma function of `self`.
+    ///
+    /// Returns NaN at the negative integers, and infinity with the sign of
+    /// zero at zero. Accurate to within 6 ULPs for `f64` and 1 ULP for
+    /// `f32`.
+    #[must_use]
+    #[inline]
+    fn gamma(self) -> Self {
+        Self::cast_from(special::gamma(self.cast()))
+    }
+
+    /// Returns the natural logarithm of the absolute value of the gamma
+    /// function of `self`, and the sign of the gamma function, 1 or -1.
+    ///
+    /// Returns infinity at zero and the negative integers. Accurate to
+    /// within 6 ULPs for `f64` and 1 ULP for `f32`, except near the zeros
+    /// of the logarithm below -2, where the error is within 3 `EPSILON`
+    /// absolute instead.
+    #[must_use]
+    #[inline]
+    fn ln_gamma(self) -> (Self, i32) {
+        let (v, sign) = special::ln_gamma(self.cast());
+        (Self::cast_from(v), sign)
+    }
+
+    /// Returns the digamma function of `self`, the derivative of
+    /// `ln_gamma`.
+    ///
+    /// Returns NaN at the negative integers. Accurate to within 3 ULPs for
+    /// `f64` and 1 ULP for `f32`, except near its zeros below zero, where
+    /// the error is within `3 * EPSILON * ln(2 - self)` absolute instead.
+    #[must_use]
+    #[inline]
+    fn digamma(self) -> Self {
+        Self::cast_from(special::digamma(self.cast()))
+    }
+
+    /// Returns the beta function of `self` and `other`,
+    /// `gamma(self) * gamma(other) / gamma(self + other)`.
+    ///
+    /// Accurate to within 16 ULPs for `f64` and 1 ULP for `f32` when both
+    /// are positive, and within 20 ULPs for `f64` and 1 ULP for `f32` when
+    /// either is negative.
+    #[must_use]
+    #[inline]
+    fn beta(self, other: Self) -> Self {
+        Self::cast_from(special::beta(self.cast(), other.cast()))
+    }
+
+    /// Returns the error function of `self`.
+    ///
+    /// Accurate to within 2 ULPs for `f64` and 1 ULP for `f32`.
+    #[must_use]
+    #[inline]
+    fn erf(self) -> Self {
+        Self::cast_from(special::erf(self.cast()))
+    }
+
+    /// Returns the complementary error function of `self`, `1 - erf(self)`,
+    /// without losing precision for large `self`.
+    ///
+    /// Accurate to within 4 ULPs for `f64` and 1 ULP for `f32`.
+    #[must_use]
+    #[inline]
+    fn erfc(self) -> Self {
+        Self::cast_from(special::erfc(self.cast()))
+    }
+
+    /// Returns the inverse error function of `self`, the `x` for which
+    /// `erf(x)` is `self`.
+    ///
+    /// Returns NaN outside `[-1, 1]`, and infinity with the sign of `self`
+    /// at -1 and 1. Accurate to within 2 ULPs for `f64` and 1 ULP for
+    /// `f32`.
+    #[must_use]
+    #[inline]
+    fn erf_inv(self) -> Self {
+        Self::cast_from(special::erf_inv(self.cast()))
+    }
 }
 
 macro_rules! impl_float {
@@ -354,6 +734,16 @@ macro_rules! impl_float {
 
                 const NEG_INFINITY: Self = Self::NEG_INFINITY;
 
+                const MAX_SAFE_INT: Self = (1_u64 << Self::MANTISSA_DIGITS) as Self;
+
+                const MIN_SAFE_INT: Self = -Self::MAX_SAFE_INT;
+
+                const EXPONENT_BITS: u32 = <$b>::BITS - Self::MANTISSA_DIGITS;
+
+                const MANTISSA_BITS: u32 = Self::MANTISSA_DIGITS - 1;
+
+                const EXPONENT_BIAS: i32 = Self::MAX_EXP - 1;
+
                 #[inline(always)]
                 fn floor(self) -> Self { Self::floor(self) }
 
@@ -554,3 +944,43 @@ impl_float!(
     f32, u32;
     f64, u64;
 );
+
+#[cfg(test)]
+mod tests {
+    use crate::Float;
+
+    #[test]
+    fn ulps_between_zeros() {
+        assert_eq!(0.0_f64.ulps_between(-0.0), 0);
+        assert_eq!((-0.0_f32).ulps_between(0.0), 0);
+        assert_eq!(0.0_f64.ulps_between(f64::from_bits(1)), 1);
+    }
+
+    #[test]
+    fn ulps_between_adjacent() {
+        assert_eq!(1.0_f64.ulps_between(1.0_f64.next_up()), 1);
+        assert_eq!(1.0_f32.next_down().ulps_between(1.0), 1);
+        assert_eq!(f64::MAX.ulps_between(f64::MAX), 0);
+    }
+
+    #[test]
+    fn ulps_between_signs() {
+        let tiny = f32::from_bits(1);
+
+        assert_eq!(tiny.ulps_between(-tiny), 2);
+        assert_eq!((-tiny).ulps_between(tiny), 2);
+        assert_eq!(1.0_f32.ulps_between(-1.0), 2 * 1.0_f32.to_bits());
+    }
+
+    #[test]
+    fn ulps_between_non_finite() {
+        assert_eq!(f64::NAN.ulps_between(1.0), u64::MAX);
+        assert_eq!(1.0_f32.ulps_between(f32::NAN), u32::MAX);
+        assert_eq!(f64::MAX.ulps_between(f64::INFINITY), 1);
+        assert_eq!(f64::INFINITY.ulps_between(f64::INFINITY), 0);
+        assert_eq!(
+            f32::NEG_INFINITY.ulps_between(f32::INFINITY),
+            2 * f32::INFINITY.to_bits()
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::{fmt, ops::Deref, str};
+
+use crate::{Float, Int, Num, ParseNumError};
+
+/// A stack buffer holding a `Float` formatted as a hexadecimal float literal.
+///
+/// Dereferences to the formatted `str`.
+///
+/// See [`Float::to_hex`].
+///
+/// [`Float::to_hex`]: crate::Float::to_hex
+#[derive(Clone, Copy)]
+pub struct HexFloatBuf {
+    buf: [u8; HexFloatBuf::CAPACITY],
+    len: usize,
+}
+
+impl HexFloatBuf {
+    /// The maximum length of any formatted `Float`, such as
+    /// `-0x1.fffffffffffffp-1022`.
+    pub const CAPACITY: usize = 24;
+
+    /// Returns the formatted value as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only ASCII signs, digits and letters are ever written to the buffer.
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    /// Returns the formatted value as a byte slice.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub(crate) fn new<F: Float>(v: F) -> Self {
+        let mut w = Self {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+        };
+
+        let (sign, biased, frac) = v.decompose();
+        let (biased, frac) = (biased.cast::<u64>(), frac.cast::<u64>());
+        let frac_bits = F::MANTISSA_BITS;
+        let exp_bits = F::EXPONENT_BITS;
+
+        if sign != F::Bits::ZERO {
+            w.push(b'-');
+        }
+
+        if biased == (1 << exp_bits) - 1 {
+            if frac == 0 {
+                w.push_bytes(b"inf");
+            } else if frac == 1 << (frac_bits - 1) {
+                w.push_bytes(b"nan");
+            } else {
+                w.push_bytes(b"nan(0x");
+                w.push_hex(frac, frac.ilog2() / 4 + 1);
+                w.push(b')');
+            }
+
+            return w;
+        }
+
+        w.push_bytes(b"0x");
+
+        if biased == 0 && frac == 0 {
+            w.push_bytes(b"0p+0");
+            return w;
+        }
+
+        // Subnormals are written as `0x0.fffp` with the smallest normal exponent.
+        w.push(if biased == 0 { b'0' } else { b'1' });
+
+        if frac != 0 {
+            // Pad the fraction to a whole number of digits and trim zeros.
+            let digits = frac_bits.div_ceil(4);
+            let frac = frac << (digits * 4 - frac_bits);
+            let zeros = frac.trailing_zeros() / 4;
+
+            w.push(b'.');
+            w.push_hex(frac >> (zeros * 4), digits - zeros);
+        }
+
+        let exp = biased.max(1) as i32 + F::MIN_EXP - 2;
+        w.push(b'p');
+        w.push(if exp < 0 { b'-' } else { b'+' });
+
+        let exp = exp.unsigned_abs();
+        let digits = exp.checked_ilog10().unwrap_or(0) + 1;
+        for i in (0..digits).rev() {
+            w.push(b'0' + (exp / 10_u32.pow(i) % 10) as u8);
+        }
+
+        w
+    }
+
+    fn push(&mut self, b: u8) {
+        self.buf[self.len] = b;
+        self.len += 1;
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    /// Writes the low `digits` hexadecimal digits of `x`.
+    fn push_hex(&mut self, x: u64, digits: u32) {
+        for i in (0..digits).rev() {
+            self.push(b"0123456789abcdef"[(x >> (i * 4)) as usize & 0xf]);
+        }
+    }
+}
+
+impl Deref for HexFloatBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for HexFloatBuf {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for HexFloatBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for HexFloatBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for HexFloatBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Returns the value of the hexadecimal digit `c`, if it is one.
+fn hex_digit(c: u8) -> Option<u64> {
+    (c as char).to_digit(16).map(u64::from)
+}
+
+/// Returns the end of the run of hexadecimal digits starting at `start`.
+fn scan_hex(s: &[u8], start: usize) -> usize {
+    start
+        + s[start..]
+            .iter()
+            .take_while(|c| c.is_ascii_hexdigit())
+            .count()
+}
+
+/// Returns whether `s` starts with `prefix`, ignoring ASCII case.
+fn starts_with(s: &[u8], prefix: &[u8]) -> bool {
+    s.len() >= prefix.len() && s[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+/// Parses `nan` with an optional `(0x...)` payload, returning its fraction
+/// bits and the number of bytes it takes up.
+///
+/// A payload that is malformed, zero or too large for the fraction is not
+/// part of the NaN.
+fn parse_nan<F: Float>(s: &[u8]) -> (u64, usize) {
+    let frac_bits = F::MANTISSA_BITS;
+    let quiet = (1 << (frac_bits - 1), 3);
+
+    if !starts_with(&s[3..], b"(0x") {
+        return quiet;
+    }
+
+    let end = scan_hex(s, 6);
+    if end == 6 || s.get(end) != Some(&b')') {
+        return quiet;
+    }
+
+    // Skip leading zeros so only the significant digits need to fit.
+    let digits = &s[6..end];
+    let digits = &digits[digits.iter().take_while(|&&c| c == b'0').count()..];
+    if digits.len() > frac_bits.div_ceil(4) as usize {
+        return quiet;
+    }
+
+    let payload = digits
+        .iter()
+        .fold(0, |acc, &c| acc << 4 | hex_digit(c).unwrap_or(0));
+    if payload == 0 || payload >> frac_bits != 0 {
+        return quiet;
+    }
+
+    (payload, end + 1)
+}
+
+/// Parses a hexadecimal float literal from the start of `s`, returning it and
+/// the number of bytes it takes up.
+pub(crate) fn parse_hex_prefix<F: Float>(s: &[u8]) -> Result<(F, usize), ParseNumError> {
+    if s.is_empty() {
+        return Err(ParseNumError::Empty);
+    }
+
+    let (negative, start) = match s[0] {
+        b'-' => (true, 1),
+        b'+' => (false, 1),
+        _ => (false, 0),
+    };
+    let sign = u64::from(negative) << (F::Bits::BITS - 1);
+    let from_bits = |bits: u64| F::from_bits(F::Bits::cast_from(bits | sign));
+
+    let frac_bits = F::MANTISSA_BITS;
+    let infinity = ((1 << F::EXPONENT_BITS) - 1) << frac_bits;
+    let rest = &s[start..];
+
+    if starts_with(rest, b"infinity") {
+        return Ok((from_bits(infinity), start + 8));
+    } else if starts_with(rest, b"inf") {
+        return Ok((from_bits(infinity), start + 3));
+    } else if starts_with(rest, b"nan") {
+        let (payload, len) = parse_nan::<F>(rest);
+        return Ok((from_bits(infinity | payload), start + len));
+    } else if !starts_with(rest, b"0x") {
+        return Err(ParseNumError::InvalidFloat);
+    }
+
+    let int_start = start + 2;
+    let int_end = scan_hex(s, int_start);
+    let (frac_start, frac_end) = if s.get(int_end) == Some(&b'.') {
+        (int_end + 1, scan_hex(s, int_end + 1))
+    } else {
+        (int_end, int_end)
+    };
+
+    if int_end == int_start && frac_end == frac_start {
+        return Err(ParseNumError::InvalidFloat);
+    }
+
+    // The binary exponent is only part of the float if it has digits.
+    let mut end = frac_end;
+    let mut exp = 0_i64;
+    if matches!(s.get(end), Some(b'p' | b'P')) {
+        let (exp_negative, exp_start) = match s.get(end + 1) {
+            Some(b'-') => (true, end + 2),
+            Some(b'+') => (false, end + 2),
+            _ => (false, end + 1),
+        };
+        let exp_len = s[exp_start..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+
+        if exp_len > 0 {
+            for &c in &s[exp_start..exp_start + exp_len] {
+                // Anything this large is zero or infinite, so stop counting.
+                if exp < 0x10000 {
+                    exp = exp * 10 + i64::from(c - b'0');
+                }
+            }
+            if exp_negative {
+                exp = -exp;
+            }
+            end = exp_start + exp_len;
+        }
+    }
+
+    // Take up to 64 significant bits, remembering whether any dropped bits
+    // were set.
+    let mut mant = 0_u64;
+    let mut sticky = false;
+    let int = s[int_start..int_end].iter().map(|&c| (c, false));
+    let frac = s[frac_start..frac_end].iter().map(|&c| (c, true));
+    for (c, frac) in int.chain(frac) {
+        let d = hex_digit(c).unwrap_or(0);
+
+        if mant == 0 && d == 0 {
+            exp -= 4 * i64::from(frac);
+        } else if mant >> 60 == 0 {
+            mant = mant << 4 | d;
+            exp -= 4 * i64::from(frac);
+        } else {
+            exp += 4 * i64::from(!frac);
+            sticky |= d != 0;
+        }
+    }
+
+    Ok((from_bits(round_to_bits::<F>(mant, exp, sticky)), end))
+}
+
+/// Returns the bits of `mant * 2^exp` rounded to nearest, ties to even, where
+/// `sticky` is whether there are set bits below `mant`.
+fn round_to_bits<F: Float>(mant: u64, exp: i64, sticky: bool) -> u64 {
+    if mant == 0 {
+        return 0;
+    }
+
+    let frac_bits = F::MANTISSA_BITS;
+    let infinite_power = (1_i64 << F::EXPONENT_BITS) - 1;
+    let infinity = (infinite_power as u64) << frac_bits;
+    let min_normal = i64::from(F::MIN_EXP) - 1;
+
+    // The exponent of the leading bit, and how many bits of `mant` fit in
+    // the float at that exponent.
+    let len = i64::from(u64::BITS - mant.leading_zeros());
+    let top = exp + len - 1;
+    let keep = i64::from(F::MANTISSA_DIGITS) - (min_normal - top).max(0);
+
+    if top - min_normal + 1 >= infinite_power {
+        return infinity;
+    } else if keep < 0 {
+        return 0;
+    }
+
+    let mant = u128::from(mant);
+    let drop = len - keep;
+    let q = if drop > 0 {
+        let q = mant >> drop;
+        let rem = mant & ((1 << drop) - 1);
+        let half = 1 << (drop - 1);
+
+        if rem > half || (rem == half && (sticky || q & 1 == 1)) {
+            q + 1
+        } else {
+            q
+        }
+    } else {
+        mant << -drop
+    };
+
+    // A carry out of the significand moves into the exponent.
+    let bits = (((top - min_normal).max(0) as u64) << frac_bits) + q as u64;
+
+    bits.min(infinity)
+}
@@ -0,0 +1,22 @@
+/// The rounding mode used when converting a `Float` to an integer.
+///
+/// See [`Float::round_with`].
+///
+/// [`Float::round_with`]: crate::Float::round_with
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round toward negative infinity, see `Float::floor`.
+    Floor,
+
+    /// Round toward positive infinity, see `Float::ceil`.
+    Ceil,
+
+    /// Round to the nearest integer, with ties away from zero, see `Float::round`.
+    NearestAway,
+
+    /// Round to the nearest integer, with ties to even, see `Float::round_ties_even`.
+    NearestEven,
+
+    /// Round toward zero, see `Float::trunc`.
+    TowardZero,
+}
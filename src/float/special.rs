@@ -0,0 +1,418 @@
+mod table;
+#[cfg(test)]
+mod tests;
+
+use std::f64::consts::{FRAC_2_SQRT_PI, PI};
+
+use table::{
+    DIGAMMA_1_2, DIGAMMA_ASYMPTOTIC, DIGAMMA_ROOT, ERFCX_0_5_2, ERFCX_2_4, ERFCX_4_INF, ERF_0,
+    ERF_INV_0, LN_GAMMA_2, STIRLING,
+};
+
+/// `ln(sqrt(2 * pi))`.
+const LN_SQRT_2PI: f64 = 0.918_938_533_204_672_8;
+
+/// `sqrt(2 * pi)`.
+const SQRT_2PI: f64 = 2.506_628_274_631_000_2;
+
+/// The argument from which the asymptotic series are accurate to `f64`.
+const ASYMPTOTIC: f64 = 10.0;
+
+/// The argument above which `gamma` overflows.
+const GAMMA_OVERFLOW: f64 = 171.624_376_956_302_7;
+
+/// The argument below which `gamma` underflows to zero.
+const GAMMA_UNDERFLOW: f64 = -200.0;
+
+/// Returns the gamma function of `x`.
+pub(crate) fn gamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        return x;
+    } else if x == 0.0 {
+        return 1.0 / x;
+    } else if x < 0.0 && x == x.floor() {
+        return f64::NAN;
+    } else if x.abs() < 0.5 {
+        return (ln_gamma_2(x) - x.ln_1p()).exp() / x;
+    } else if x > 0.0 {
+        return gamma_positive(x);
+    }
+
+    // Reflect with `gamma(x) * gamma(-x) == -pi / (x * sin(pi * x))`, where
+    // `-x` is exact, unlike the usual `1 - x`.
+    let s = sin_pi(x);
+    if x < GAMMA_UNDERFLOW {
+        return 0.0_f64.copysign(s);
+    }
+
+    let r = -PI / (x * s);
+    if -x < ASYMPTOTIC {
+        r / gamma_positive(-x)
+    } else {
+        // Divide by each part, as `gamma(-x)` may overflow when the result
+        // does not.
+        let (p, q) = stirling_parts(-x);
+        r / p / q
+    }
+}
+
+/// Returns the gamma function of `x`, which is at least 0.5.
+fn gamma_positive(x: f64) -> f64 {
+    if x > GAMMA_OVERFLOW {
+        f64::INFINITY
+    } else if x < 1.5 {
+        let z = x - 1.0;
+        (ln_gamma_2(z) - z.ln_1p()).exp()
+    } else if x < ASYMPTOTIC {
+        // `x - 1` is exact, so the only rounding is in the product.
+        let (mut y, mut p) = (x, 1.0);
+        while y > 2.5 {
+            y -= 1.0;
+            p *= y;
+        }
+
+        p * ln_gamma_2(y - 2.0).exp()
+    } else {
+        let (p, q) = stirling_parts(x);
+        p * q
+    }
+}
+
+/// Splits Stirling's approximation of `gamma(x)` into two factors that do
+/// not overflow for any `x` up to `-GAMMA_UNDERFLOW`.
+fn stirling_parts(x: f64) -> (f64, f64) {
+    // `x` is exact as the base, so rounding is not magnified by the power.
+    let p = x.powf((x - 0.5) / 2.0);
+    let q = p * (-x).exp() * (SQRT_2PI * stirling(x).exp());
+
+    (p, q)
+}
+
+/// Returns the natural logarithm of the absolute value of the gamma function
+/// of `x`, and the sign of the gamma function.
+pub(crate) fn ln_gamma(x: f64) -> (f64, i32) {
+    if x.is_nan() {
+        return (x, 1);
+    } else if x.is_infinite() {
+        return (f64::INFINITY, 1);
+    } else if x <= 0.0 && x == x.floor() {
+        // Poles have the sign of the side of zero they are on.
+        let sign = if x.is_sign_negative() && x == 0.0 {
+            -1
+        } else {
+            1
+        };
+        return (f64::INFINITY, sign);
+    }
+
+    let sign = if x < 0.0 && sin_pi(x) < 0.0 { -1 } else { 1 };
+    if x.abs() < 0.5 {
+        return (ln_gamma_2(x) - x.ln_1p() - x.abs().ln(), sign);
+    } else if x > 0.0 {
+        return (ln_gamma_positive(x), sign);
+    }
+
+    let v = (PI / (x * sin_pi(x)).abs()).ln() - ln_gamma_positive(-x);
+    (v, sign)
+}
+
+/// Returns `ln(gamma(x))` for `x` of at least 0.5.
+fn ln_gamma_positive(x: f64) -> f64 {
+    if x < 1.5 {
+        // `ln_gamma` is zero at one and two, and `x - 1` and `x - 2` are
+        // exact, which keeps the result accurate near them.
+        let z = x - 1.0;
+        ln_gamma_2(z) - z.ln_1p()
+    } else if x <= 2.5 {
+        ln_gamma_2(x - 2.0)
+    } else if x < ASYMPTOTIC {
+        let (mut y, mut p) = (x, 1.0);
+        while y > 2.5 {
+            y -= 1.0;
+            p *= y;
+        }
+
+        p.ln() + ln_gamma_2(y - 2.0)
+    } else {
+        (x - 0.5) * x.ln() - x + LN_SQRT_2PI + stirling(x)
+    }
+}
+
+/// Returns `ln(gamma(2 + z))` for `|z| <= 0.5`.
+fn ln_gamma_2(z: f64) -> f64 {
+    z * horner(&LN_GAMMA_2, z)
+}
+
+/// Returns the sum of the terms after the first few of Stirling's series for
+/// `ln(gamma(x))`, for `x` of at least `ASYMPTOTIC`.
+fn stirling(x: f64) -> f64 {
+    let r = 1.0 / x;
+    r * horner(&STIRLING, r * r)
+}
+
+/// Returns the digamma function of `x`.
+pub(crate) fn digamma(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        return x;
+    } else if x == 0.0 {
+        return -1.0 / x;
+    } else if x < 0.0 && x == x.floor() {
+        return f64::NAN;
+    } else if x > 0.0 {
+        return digamma_positive(x);
+    }
+
+    // Reflect with `digamma(1 - x) - digamma(x) == pi * cot(pi * x)`.
+    digamma_positive(1.0 - x) - PI * cos_pi(x) / sin_pi(x)
+}
+
+/// Returns the digamma function of the positive `x`.
+fn digamma_positive(x: f64) -> f64 {
+    if x < 1.0 {
+        digamma_positive(x + 1.0) - 1.0 / x
+    } else if x <= 2.0 {
+        // Factor out the root so the result is accurate near it.
+        let (hi, lo) = DIGAMMA_ROOT;
+        ((x - hi) - lo) * chebyshev(&DIGAMMA_1_2, 2.0 * x - 3.0)
+    } else if x < ASYMPTOTIC {
+        let (mut y, mut sum) = (x, 0.0);
+        while y > 2.0 {
+            y -= 1.0;
+            sum += 1.0 / y;
+        }
+
+        digamma_positive(y) + sum
+    } else {
+        let r = 1.0 / (x * x);
+        x.ln() - 0.5 / x - r * horner(&DIGAMMA_ASYMPTOTIC, r)
+    }
+}
+
+/// Returns the beta function of `a` and `b`.
+pub(crate) fn beta(a: f64, b: f64) -> f64 {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    let (s, e) = two_sum(a, b);
+
+    if a.is_nan() || b.is_nan() {
+        return f64::NAN;
+    } else if s.is_infinite() {
+        return if a > 0.0 { 0.0 } else { f64::NAN };
+    } else if s <= 0.0 && s == s.floor() && a != a.floor() && b != b.floor() {
+        // `1 / gamma(s)` is zero.
+        return 0.0;
+    }
+
+    // Gamma overflows above `GAMMA_OVERFLOW`, and is subnormal at best below
+    // `2 - GAMMA_OVERFLOW`.
+    let underflows = |x: f64| x <= 2.0 - GAMMA_OVERFLOW;
+    if a < 0.0 && (b >= GAMMA_OVERFLOW || underflows(a) || underflows(s)) {
+        if a == a.floor() {
+            return f64::NAN;
+        }
+
+        // Reflect with `beta(a, b) * beta(-a, s) == -pi / (a * sin(pi * a))`,
+        // which only takes the gamma function of smaller values. The sum of
+        // `-a` and `s` is `b - e`, so correct both for the rounding of `s`.
+        let reflected = beta(-a, s) * gamma_shift(s, e) * gamma_shift(b, -e);
+        return -PI / (a * sin_pi(a)) / reflected;
+    } else if s >= GAMMA_OVERFLOW {
+        return beta_large(a, b, s, e);
+    }
+
+    let (ga, gb, gs) = (gamma(a), gamma(b), gamma(s) * gamma_shift(s, e));
+
+    // If the result is in range, one of these orders keeps the intermediate
+    // product in range too.
+    let v = gb / gs * ga;
+    if v.is_finite() && v != 0.0 {
+        v
+    } else {
+        ga / gs * gb
+    }
+}
+
+/// Returns `gamma(x + e) / gamma(x)` for `e` much smaller than `x`, which
+/// corrects for rounding in `x`.
+fn gamma_shift(x: f64, e: f64) -> f64 {
+    if e == 0.0 {
+        return 1.0;
+    } else if x > 0.5 {
+        return (digamma(x) * e).exp();
+    }
+
+    // This is `exp(digamma(x) * e)` to first order, but `x` may be close
+    // enough to a pole for that to fail, so take the pole's factor exactly.
+    // The distance `d` to it is exact.
+    let d = x - x.round();
+    ((digamma(x) + 1.0 / d) * e).exp() * (d / (d + e))
+}
+
+/// Returns the beta function of `a` and `b` whose sum `s + e` is at least
+/// `GAMMA_OVERFLOW`, where `0 <= a <= b`.
+fn beta_large(a: f64, b: f64, s: f64, e: f64) -> f64 {
+    if a < ASYMPTOTIC {
+        // `gamma(b) / gamma(s)` from Stirling's series.
+        let v = (a + stirling(b) - stirling(s)).exp();
+        let ratio = v * pow_ratio(b, s, e, b - 0.5) * pow_ratio(1.0, s, e, a);
+
+        return gamma(a) * ratio;
+    }
+
+    let v = stirling(a) + stirling(b) - stirling(s);
+    let root = (s / (a * b)).sqrt();
+
+    SQRT_2PI * root * v.exp() * pow_ratio(a, s, e, a) * pow_ratio(b, s, e, b)
+}
+
+/// Returns `(n / (d + e))^p`, where `e` is much smaller than `d`, without
+/// the rounding of the ratio magnified by a large `p`.
+fn pow_ratio(n: f64, d: f64, e: f64, p: f64) -> f64 {
+    // The ratio is `q * (1 + rho)` to first order, where the remainder of
+    // the division is exact.
+    let q = n / d;
+    let rho = ((-q).mul_add(d, n) / q - e) / d;
+
+    q.powf(p) * (p * rho).exp()
+}
+
+/// Returns the error function of `x`.
+pub(crate) fn erf(x: f64) -> f64 {
+    if x.abs() < 0.5 {
+        x * FRAC_2_SQRT_PI * horner(&ERF_0, x * x)
+    } else if x.is_nan() {
+        x
+    } else {
+        (1.0 - erfc_large(x.abs())).copysign(x)
+    }
+}
+
+/// Returns the complementary error function of `x`, `1 - erf(x)`.
+pub(crate) fn erfc(x: f64) -> f64 {
+    if x.abs() < 0.5 {
+        1.0 - erf(x)
+    } else if x.is_nan() {
+        x
+    } else if x < 0.0 {
+        2.0 - erfc_large(-x)
+    } else {
+        erfc_large(x)
+    }
+}
+
+/// Returns `erfc(x)` for `x` of at least 0.5.
+fn erfc_large(x: f64) -> f64 {
+    if x > 28.0 {
+        return 0.0;
+    }
+
+    // Expand the smooth `exp(x^2) * erfc(x)`, in `1 / x` for large `x`.
+    let scaled = if x < 2.0 {
+        chebyshev(&ERFCX_0_5_2, (4.0 * x - 5.0) / 3.0)
+    } else if x < 4.0 {
+        chebyshev(&ERFCX_2_4, x - 3.0)
+    } else {
+        chebyshev(&ERFCX_4_INF, 8.0 / x - 1.0) / x
+    };
+
+    exp_neg_square(x) * scaled
+}
+
+/// Returns the inverse error function of `y`.
+pub(crate) fn erf_inv(y: f64) -> f64 {
+    let a = y.abs();
+    if a <= 0.75 {
+        return y * chebyshev(&ERF_INV_0, y * y * (32.0 / 9.0) - 1.0);
+    } else if y.is_nan() || a > 1.0 {
+        return f64::NAN;
+    } else if a == 1.0 {
+        return f64::INFINITY.copysign(y);
+    }
+
+    // Start from Winitzki's approximation, good to about three digits.
+    const K: f64 = 0.147;
+    let l = ((1.0 - a) * (1.0 + a)).ln();
+    let t = 2.0 / (PI * K) + l / 2.0;
+    let mut x = ((t * t - l / K).sqrt() - t).sqrt();
+
+    // Refine with Halley's method, which triples the correct digits each
+    // step, comparing `erfc` with `1 - a`, which is exact.
+    for _ in 0..8 {
+        let f = (1.0 - a) - erfc(x);
+        let ratio = f / (FRAC_2_SQRT_PI * exp_neg_square(x));
+        let step = ratio / x.mul_add(ratio, 1.0);
+
+        x -= step;
+        if step.abs() <= x * f64::EPSILON {
+            break;
+        }
+    }
+
+    x.copysign(y)
+}
+
+/// Returns `exp(-x^2)` without the rounding error of `x^2` magnified by the
+/// exponential.
+fn exp_neg_square(x: f64) -> f64 {
+    let hi = x * x;
+    let lo = x.mul_add(x, -hi);
+
+    (-hi).exp() * (1.0 - lo)
+}
+
+/// Returns `sin(pi * x)`, which is exactly zero at integers.
+fn sin_pi(x: f64) -> f64 {
+    let (r, n) = reduce_pi(x);
+    match n {
+        0 => r.sin(),
+        1 => r.cos(),
+        2 => -r.sin(),
+        _ => -r.cos(),
+    }
+}
+
+/// Returns `cos(pi * x)`, which is exactly zero at half-integers.
+fn cos_pi(x: f64) -> f64 {
+    let (r, n) = reduce_pi(x);
+    match n {
+        0 => r.cos(),
+        1 => -r.sin(),
+        2 => -r.cos(),
+        _ => r.sin(),
+    }
+}
+
+/// Returns `pi * r` and the quarter turn `n` modulo 4 such that `x` is
+/// `n / 2 + r` modulo 2, where `|r| <= 1/4`.
+fn reduce_pi(x: f64) -> (f64, i64) {
+    // Both the remainder and `x - n / 2` are exact.
+    let x = x % 2.0;
+    let n = (2.0 * x).round();
+
+    ((x - n / 2.0) * PI, (n as i64).rem_euclid(4))
+}
+
+/// Returns `a + b` and its rounding error, such that the two sum to it
+/// exactly.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// Evaluates the polynomial with coefficients `c`, from the constant term,
+/// at `x`.
+fn horner(c: &[f64], x: f64) -> f64 {
+    c.iter().rev().fold(0.0, |acc, &k| acc * x + k)
+}
+
+/// Evaluates the Chebyshev series with coefficients `c` at `t` in `[-1, 1]`
+/// with Clenshaw's recurrence.
+fn chebyshev(c: &[f64], t: f64) -> f64 {
+    let (mut b1, mut b2) = (0.0, 0.0);
+    for &k in c[1..].iter().rev() {
+        (b1, b2) = (2.0 * t * b1 - b2 + k, b1);
+    }
+
+    t * b1 - b2 + c[0]
+}
@@ -0,0 +1,218 @@
+/// Coefficients of Stirling's series for `ln_gamma`, `B_2k / (2k (2k - 1))`,
+/// in powers of `1 / x^2` after `1 / x`.
+pub(crate) const STIRLING: [f64; 10] = [
+    1.0 / 12.0,
+    -1.0 / 360.0,
+    1.0 / 1260.0,
+    -1.0 / 1680.0,
+    1.0 / 1188.0,
+    -691.0 / 360360.0,
+    1.0 / 156.0,
+    -3617.0 / 122400.0,
+    43867.0 / 244188.0,
+    -174611.0 / 125400.0,
+];
+
+/// Coefficients of the asymptotic series for `digamma`, `B_2k / 2k`, in
+/// powers of `1 / x^2` after `1 / x^2`.
+pub(crate) const DIGAMMA_ASYMPTOTIC: [f64; 8] = [
+    1.0 / 12.0,
+    -1.0 / 120.0,
+    1.0 / 252.0,
+    -1.0 / 240.0,
+    1.0 / 132.0,
+    -691.0 / 32760.0,
+    1.0 / 12.0,
+    -3617.0 / 8160.0,
+];
+
+/// The positive root of the digamma function, split into the closest `f64`
+/// and the remainder.
+pub(crate) const DIGAMMA_ROOT: (f64, f64) = (1.4616321449683622, 9.549995429965697e-17);
+
+/// Taylor coefficients of `ln_gamma(2 + z)` from `z^1`, which are `1 - γ`
+/// then `(-1)^k (ζ(k) - 1) / k`, enough for `|z| <= 0.5`.
+#[rustfmt::skip]
+pub(crate) const LN_GAMMA_2: [f64; 28] = [
+    0.42278433509846713,
+    0.3224670334241132,
+    -0.0673523010531981,
+    0.020580808427784546,
+    -0.007385551028673986,
+    0.0028905103307415234,
+    -0.001192753911703261,
+    0.0005096695247430425,
+    -0.00022315475845357939,
+    9.945751278180853e-5,
+    -4.492623673813314e-5,
+    2.050721277567069e-5,
+    -9.439488275268397e-6,
+    4.374866789907488e-6,
+    -2.039215753801366e-6,
+    9.55141213040742e-7,
+    -4.492469198764566e-7,
+    2.1207184805554665e-7,
+    -1.0043224823968099e-7,
+    4.7698101693639804e-8,
+    -2.2711094608943164e-8,
+    1.0838659214896955e-8,
+    -5.183475041970047e-9,
+    2.4836745438024785e-9,
+    -1.1921401405860912e-9,
+    5.731367241678862e-10,
+    -2.7595228851242334e-10,
+    1.330476437424449e-10,
+];
+
+/// Chebyshev coefficients of `digamma(x) / (x - DIGAMMA_ROOT)` on `[1, 2]`.
+#[rustfmt::skip]
+pub(crate) const DIGAMMA_1_2: [f64; 24] = [
+    0.983570260441567,
+    -0.2270640863346444,
+    0.03336889902393011,
+    -0.005320077271596548,
+    0.0008788656937820505,
+    -0.00014773492650606044,
+    2.5060543468359146e-5,
+    -4.2721034029993324e-6,
+    7.302769272465923e-7,
+    -1.2502886813559594e-7,
+    2.1425009322359472e-8,
+    -3.673295918565179e-9,
+    6.299717236108931e-10,
+    -1.080593028883266e-10,
+    1.853735192053638e-11,
+    -3.18023536582553e-12,
+    5.456147570322795e-13,
+    -9.360993131036438e-14,
+    1.606064636295075e-14,
+    -2.7555431377438815e-15,
+    4.727736178601912e-16,
+    -8.11148420346509e-17,
+    1.391707769474154e-17,
+    -2.3877901082188625e-18,
+];
+
+/// Taylor coefficients of `erf(x) * sqrt(pi) / (2 * x)` in `x^2`, which are
+/// `(-1)^n / (n! (2n + 1))`, enough for `|x| < 0.5`.
+#[rustfmt::skip]
+pub(crate) const ERF_0: [f64; 13] = [
+    1.0,
+    -0.3333333333333333,
+    0.1,
+    -0.023809523809523808,
+    0.004629629629629629,
+    -0.0007575757575757576,
+    0.00010683760683760684,
+    -1.3227513227513228e-5,
+    1.4589169000933706e-6,
+    -1.4503852223150468e-7,
+    1.3122532963802806e-8,
+    -1.0892221037148573e-9,
+    8.35070279514724e-11,
+];
+
+/// Chebyshev coefficients of `erf_inv(y) / y` in `y^2` on `[0, 0.5625]`,
+/// which covers `|y| <= 0.75`.
+#[rustfmt::skip]
+pub(crate) const ERF_INV_0: [f64; 24] = [
+    0.9744859196197088,
+    0.09767121365429762,
+    0.0106798426748877,
+    0.00145839261934236,
+    0.00022139561793534646,
+    3.5754642695386944e-5,
+    6.013052435846527e-6,
+    1.0405605281919531e-6,
+    1.8393099988652242e-7,
+    3.3048595802566894e-8,
+    6.015922964924221e-9,
+    1.1067505460742694e-9,
+    2.0540500062873634e-10,
+    3.8404854958353785e-11,
+    7.226135143013051e-12,
+    1.3670906380006421e-12,
+    2.598708098538055e-13,
+    4.960649711523937e-14,
+    9.504612363589054e-15,
+    1.8271407438754928e-15,
+    3.5229350975383005e-16,
+    6.810937482440025e-17,
+    1.319990081950303e-17,
+    2.5639064134405433e-18,
+];
+
+/// Chebyshev coefficients of `exp(x^2) * erfc(x)` on `[0.5, 2]`.
+#[rustfmt::skip]
+pub(crate) const ERFCX_0_5_2: [f64; 21] = [
+    0.4006822530639845,
+    -0.17395587897748596,
+    0.03383702540740585,
+    -0.006032263577025819,
+    0.001000274118215681,
+    -0.0001558965252433288,
+    2.3014442396758424e-5,
+    -3.2375421924541957e-6,
+    4.360702485858127e-7,
+    -5.645684833789816e-8,
+    7.048623263203286e-9,
+    -8.509612327566787e-10,
+    9.957554366871612e-11,
+    -1.1316613855047257e-11,
+    1.2513369992930791e-12,
+    -1.3483678086194079e-13,
+    1.4178345509581745e-14,
+    -1.4566964654246537e-15,
+    1.4639564848603663e-16,
+    -1.4406081509350508e-17,
+    1.3893934243911832e-18,
+];
+
+/// Chebyshev coefficients of `exp(x^2) * erfc(x)` on `[2, 4]`.
+#[rustfmt::skip]
+pub(crate) const ERFCX_2_4: [f64; 20] = [
+    0.1874295690262241,
+    -0.05794652883669097,
+    0.008595247387396534,
+    -0.0012284613640242594,
+    0.00016974366686180488,
+    -2.2738329337044562e-5,
+    2.959844378804878e-6,
+    -3.7513575581379714e-7,
+    4.6372828761738244e-8,
+    -5.599455392564088e-9,
+    6.613160896679916e-10,
+    -7.648271522199816e-11,
+    8.670904325876594e-12,
+    -9.645456274382108e-13,
+    1.0536827959360625e-13,
+    -1.1312630906760667e-14,
+    1.1945180669174577e-15,
+    -1.241310752580407e-16,
+    1.270246323185436e-17,
+    -1.280725873663855e-18,
+];
+
+/// Chebyshev coefficients of `x * exp(x^2) * erfc(x)` in `1 / x` on `[0, 0.25]`.
+#[rustfmt::skip]
+pub(crate) const ERFCX_4_INF: [f64; 19] = [
+    0.5579806323959606,
+    -0.008176370652593417,
+    -0.001893800698918767,
+    8.135191807225853e-5,
+    6.886433482931834e-6,
+    -8.66842697260929e-7,
+    -1.0605985681160552e-8,
+    9.128665955359679e-9,
+    -5.137073029655299e-10,
+    -7.515699719704348e-11,
+    1.3379723228335024e-11,
+    -6.823772973728953e-14,
+    -2.121390789408522e-13,
+    2.2951219659613427e-14,
+    1.4778079350327186e-15,
+    -6.23994341272092e-16,
+    4.380197359621281e-17,
+    8.250479751101904e-18,
+    -2.0854455199042304e-18,
+];
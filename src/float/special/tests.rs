@@ -0,0 +1,392 @@
+use std::f64::consts::PI;
+
+use crate::{assert_approx_eq, assert_ulps_eq, Float, Tolerance};
+
+// Reference values computed with 60 significant digits and rounded to each
+// type, at inputs exact in `f32` so each row serves both types.
+
+/// Checks `f` for both float types against a table of `(x, f64, f32)`,
+/// within `ulps` for `f64` and 1 ULP for `f32`.
+fn check(table: &[(f64, f64, f32)], ulps: u64, f: fn(f64) -> f64, g: fn(f32) -> f32) {
+    for &(x, want, want32) in table {
+        assert_ulps_eq!(f(x), want, ulps);
+        assert_ulps_eq!(g(x as f32), want32, 1);
+    }
+}
+
+#[rustfmt::skip]
+const GAMMA: [(f64, f64, f32); 31] = [
+    (1.0000000031710769e-30, 9.999999968289232e29, 1e30),
+    (9.999999747378752e-6, 99999.42532043811, 99999.42),
+    (0.10000000149011612, 9.513507550899174, 9.513508),
+    (0.5, 1.772453850905516, 1.7724539),
+    (1.0, 1.0, 1.0),
+    (1.25, 0.906402477055477, 0.90640247),
+    (1.5, 0.886226925452758, 0.88622695),
+    (2.0, 1.0, 1.0),
+    (2.5, 1.329340388179137, 1.3293403),
+    (3.75, 4.422988410460251, 4.4229884),
+    (5.0, 24.0, 24.0),
+    (7.125, 910.9984887224346, 910.9985),
+    (9.875, 274082.281896993, 274082.28),
+    (10.5, 1133278.3889487856, 1133278.4),
+    (20.25, 2.5604013332847648e17, 2.5604013e17),
+    (33.5, 1.505856975626702e36, 1.505857e36),
+    (34.5, 5.044620868349451e37, 5.0446206e37),
+    (56.75, 2.5947514600692585e74, f32::INFINITY),
+    (100.5, 9.320963104082716e156, f32::INFINITY),
+    (170.5, 5.56209241456e305, f32::INFINITY),
+    (171.5, 9.4833675668248e307, f32::INFINITY),
+    (-9.999999747378752e-6, -100000.5797517681, -100000.58),
+    (-0.5, -3.544907701811032, -3.5449078),
+    (-1.5, 2.363271801207355, 2.3632717),
+    (-2.25, -1.7428148657282527, -1.7428149),
+    (-3.75, 0.2678661288614166, 0.26786613),
+    (-10.125, -1.6848312620525174e-6, -1.6848313e-6),
+    (-33.25, 2.1247598917999635e-37, 2.12476e-37),
+    (-42.5, -3.41979352072488e-52, 0.0),
+    (-170.5, -3.3127395215386074e-308, 0.0),
+    (-185.25, 0.0, 0.0),
+];
+
+#[rustfmt::skip]
+const LN_GAMMA: [(f64, f64, f32, i32); 31] = [
+    (1.0000000031710769e-30, 69.07755278665029, 69.07755, 1),
+    (9.999999747378752e-6, 11.512919718158097, 11.512919, 1),
+    (0.10000000149011612, 2.252712636201601, 2.2527127, 1),
+    (0.5, 0.5723649429247001, 0.5723649, 1),
+    (0.9990000128746033, 0.0005780310802754651, 0.00057803106, 1),
+    (1.0, 0.0, 0.0, 1),
+    (1.0010000467300415, -0.0005764204947818381, -0.0005764205, 1),
+    (1.5, -0.12078223763524522, -0.12078224, 1),
+    (1.9989999532699585, -0.00042248152727373384, -0.00042248153, 1),
+    (2.0, 0.0, 0.0, 1),
+    (2.000999927520752, 0.00042307604498146537, 0.00042307604, 1),
+    (2.5, 0.2846828704729192, 0.28468287, 1),
+    (3.75, 1.486815578593417, 1.4868156, 1),
+    (7.125, 6.814541238336996, 6.8145413, 1),
+    (10.5, 13.940625219403763, 13.940625, 1),
+    (33.5, 83.30242550295006, 83.30243, 1),
+    (100.5, 361.4355404677776, 361.43555, 1),
+    (10000.0, 82099.71749644238, 82099.72, 1),
+    (10000000000.0, 220258509288.81058, 2.2025852e11, 1),
+    (1.0000000150474662e30, 6.807755382926351e31, 6.8077553e31, 1),
+    (9.999999616903162e35, 8.18930601721786e37, 8.189306e37, 1),
+    (-9.999999747378752e-6, 11.512931262471104, 11.512931, -1),
+    (-0.5, 1.2655121234846454, 1.2655121, -1),
+    (-1.5, 0.860047015376481, 0.86004704, 1),
+    (-2.25, 0.5555015450206475, 0.5555015, -1),
+    (-3.75, -1.3172679424463636, -1.3172679, 1),
+    (-10.125, -13.29384514038954, -13.293845, -1),
+    (-33.25, -84.44198963688153, -84.441986, 1),
+    (-170.5, -707.9984331450788, -707.9984, -1),
+    (-1000.5, -5914.437701116852, -5914.4375, -1),
+    (-999999.5, -12815510.33217288, -1.281551e7, 1),
+];
+
+#[rustfmt::skip]
+const LN_GAMMA_NEAR_ZERO: [(f64, f64, f32, i32); 5] = [
+    (-2.4570000171661377, 3.747028477551427e-5, 3.7470283e-5, -1),
+    (-2.749500036239624, 0.00351086719921521, 0.0035108672, -1),
+    (-3.143699884414673, -0.0009256480658757961, -0.0009256481, 1),
+    (-3.955199956893921, -0.0019527162354994983, -0.0019527163, 1),
+    (-4.039599895477295, -0.006359325346612951, -0.0063593253, -1),
+];
+
+#[rustfmt::skip]
+const DIGAMMA: [(f64, f64, f32); 26] = [
+    (1.0000000031710769e-30, -9.999999968289232e29, -1e30),
+    (9.999999747378752e-6, -100000.57972542822, -100000.58),
+    (0.10000000149011612, -10.423754789263684, -10.423755),
+    (0.5, -1.9635100260214235, -1.96351),
+    (1.0, -0.5772156649015329, -0.5772157),
+    (1.25, -0.22745353337626542, -0.22745353),
+    (1.4616321325302124, -1.203605254910667e-8, -1.2036052e-8),
+    (1.5, 0.03648997397857652, 0.036489975),
+    (2.0, 0.42278433509846713, 0.42278433),
+    (2.5, 0.7031566406452432, 0.70315665),
+    (3.75, 1.1825373886117962, 1.1825374),
+    (7.125, 1.8917959606533552, 1.891796),
+    (9.99899959564209, 2.251647374676426, 2.2516475),
+    (10.0, 2.251752589066721, 2.2517526),
+    (33.5, 3.496545816722825, 3.4965458),
+    (10000.0, 9.210290371142849, 9.21029),
+    (10000000000.0, 23.025850929890456, 23.02585),
+    (1.0000000150474662e30, 69.07755280486883, 69.07755),
+    (-9.999999747378752e-6, 99999.42529409818, 99999.42),
+    (-0.25, 2.9141391202135276, 2.914139),
+    (-1.25, 3.714139120213528, 3.7141392),
+    (-2.75, -1.959055264977997, -1.9590553),
+    (-3.125, 8.875460052635312, 8.87546),
+    (-10.125, 9.948053825866076, 9.948053),
+    (-33.75, 0.39212942538433426, 0.39212942),
+    (-1000.25, 10.050097693066705, 10.050097),
+];
+
+#[rustfmt::skip]
+const DIGAMMA_NEAR_ZERO: [(f64, f64, f32); 6] = [
+    (-0.504082977771759, 2.7259856236381173e-7, 2.7259856e-7),
+    (-1.5734983682632446, 1.0428562007081108e-6, 1.0428562e-6),
+    (-2.6107208728790283, -4.811107649295407e-8, -4.8111076e-8),
+    (-3.6352932453155518, 1.4111772478281014e-6, 1.4111772e-6),
+    (-9.703300476074219, -0.00953869537851964, -0.009538695),
+    (-49.77000045776367, 0.3539772555431903, 0.35397726),
+];
+
+#[rustfmt::skip]
+const ERF: [(f64, f64, f32); 20] = [
+    (1.0000000031710769e-30, 1.1283791706736896e-30, 1.1283791e-30),
+    (9.99999993922529e-9, 1.1283791602378209e-8, 1.1283792e-8),
+    (0.0010000000474974513, 0.0011283788445643173, 0.0011283788),
+    (0.10000000149011612, 0.11246291768297051, 0.112462915),
+    (0.25, 0.27632639016823696, 0.2763264),
+    (0.4999000132083893, 0.5204120067698496, 0.520412),
+    (0.5, 0.5204998778130465, 0.5204999),
+    (0.75, 0.7111556336535151, 0.71115565),
+    (1.0, 0.8427007929497149, 0.8427008),
+    (1.5, 0.9661051464753108, 0.96610516),
+    (2.0, 0.9953222650189527, 0.9953223),
+    (2.5, 0.999593047982555, 0.999593),
+    (3.0, 0.9999779095030014, 0.9999779),
+    (4.0, 0.9999999845827421, 1.0),
+    (5.0, 0.9999999999984626, 1.0),
+    (6.0, 1.0, 1.0),
+    (-0.10000000149011612, -0.11246291768297051, -0.112462915),
+    (-0.5, -0.5204998778130465, -0.5204999),
+    (-1.0, -0.8427007929497149, -0.8427008),
+    (-3.0, -0.9999779095030014, -0.9999779),
+];
+
+#[rustfmt::skip]
+const ERFC: [(f64, f64, f32); 26] = [
+    (1.0000000031710769e-30, 1.0, 1.0),
+    (0.10000000149011612, 0.8875370823170295, 0.88753706),
+    (0.4999000132083893, 0.47958799323015044, 0.479588),
+    (0.5, 0.4795001221869535, 0.47950011),
+    (0.75, 0.28884436634648486, 0.28884438),
+    (1.0, 0.15729920705028513, 0.1572992),
+    (1.5, 0.033894853524689274, 0.03389485),
+    (2.0, 0.004677734981047266, 0.004677735),
+    (3.0, 2.209049699858544e-5, 2.2090497e-5),
+    (3.9999001026153564, 1.5429948174796422e-8, 1.5429949e-8),
+    (4.0, 1.541725790028002e-8, 1.5417259e-8),
+    (5.0, 1.537459794428035e-12, 1.5374598e-12),
+    (8.0, 1.1224297172982926e-29, 1.1224297e-29),
+    (9.5, 3.7692144856548797e-41, 3.7692e-41),
+    (10.0, 2.088487583762545e-45, 1e-45),
+    (15.0, 7.212994172451207e-100, 0.0),
+    (20.0, 5.395865611607901e-176, 0.0),
+    (26.0, 5.663192408856143e-296, 0.0),
+    (26.5, 2.2109076642637343e-307, 0.0),
+    (27.0, 5.23705e-319, 0.0),
+    (27.25, 0.0, 0.0),
+    (-0.10000000149011612, 1.1124629176829706, 1.1124629),
+    (-0.5, 1.5204998778130465, 1.5204998),
+    (-1.0, 1.8427007929497148, 1.8427008),
+    (-3.0, 1.9999779095030015, 1.999978),
+    (-6.0, 2.0, 2.0),
+];
+
+#[rustfmt::skip]
+const ERF_INV: [(f64, f64, f32); 19] = [
+    (1.0000000031710769e-30, 8.862269282630517e-31, 8.862269e-31),
+    (9.99999993922529e-9, 8.862269200667396e-9, 8.862269e-9),
+    (0.0010000000474974513, 0.0008862271995601054, 0.0008862272),
+    (0.008085999637842178, 0.007166153265987862, 0.0071661533),
+    (0.10000000149011612, 0.08885599182530649, 0.08885599),
+    (0.25, 0.2253120550121781, 0.22531205),
+    (0.5, 0.4769362762044699, 0.47693628),
+    (0.7400000095367432, 0.7964788215503785, 0.7964788),
+    (0.7599999904632568, 0.8308411116192328, 0.8308411),
+    (0.8999999761581421, 1.1630870719457722, 1.1630871),
+    (0.9900000095367432, 1.8213866009002777, 1.8213866),
+    (0.9990000128746033, 2.3267563267961546, 2.3267562),
+    (0.9998999834060669, 2.7510354379032873, 2.7510355),
+    (0.9999989867210388, 3.45707472603595, 3.4570746),
+    (0.9999999403953552, 3.832506856900711, 3.832507),
+    (-0.10000000149011612, -0.08885599182530649, -0.08885599),
+    (-0.5, -0.4769362762044699, -0.47693628),
+    (-0.8999999761581421, -1.1630870719457722, -1.1630871),
+    (-0.9990000128746033, -2.3267563267961546, -2.3267562),
+];
+
+#[rustfmt::skip]
+const BETA: [(f64, f64, f64, f32); 27] = [
+    (0.5, 0.5, PI, PI as f32),
+    (1.0, 1.0, 1.0, 1.0),
+    (1.0, 3.0, 0.3333333333333333, 0.33333334),
+    (2.5, 3.5, 0.03681553890925539, 0.03681554),
+    (0.10000000149011612, 7.25, 7.853281705313904, 7.8532815),
+    (9.999999747378752e-6, 2.0, 99999.00253621244, 99999.0),
+    (9.5, 8.25, 5.735918393358028e-6, 5.7359184e-6),
+    (30.5, 40.25, 5.967885608343021e-22, 5.9678855e-22),
+    (100.0, 100.0, 2.2087606931995024e-61, 0.0),
+    (0.5, 1000.5, 0.056042906363122376, 0.056042906),
+    (9.25, 100000.0, 3.884646246203431e-42, 3.884e-42),
+    (50.5, 1000000.0, 4.2851038091392394e-240, 0.0),
+    (1000.0, 1000000000.0, 0.0, 0.0),
+    (-0.5, 1.75, -3.594420704206777, -3.5944207),
+    (-2.25, 3.5, -6.390081251923158, -6.3900814),
+    (-1.5, -2.25, -15.3761330124401, -15.376133),
+    (-3.75, 0.625, 0.32931202459532316, 0.32931203),
+    (-10.5, 200.25, -2.849252882999742e17, -2.849253e17),
+    (-2.5, 174.0, -368098.23563033063, -368098.25),
+    (-100.5, 300.0, -1.2247445137245387e82, f32::NEG_INFINITY),
+    (-170.5, 172.0, -46.38958138236792, -46.38958),
+    (-190.5, 170.0, 3.5388340867137875e-30, 3.5388342e-30),
+    (-180.5, 0.30000001192092896, 0.3697617089372301, 0.3697617),
+    (-300.5, 250.25, 5.555399890730289e-61, 0.0),
+    (-1.100000023841858, 736.5, 13824.281622403532, 13824.281),
+    (-150.25, -20.5, -4.857621606390063e26, -4.8576217e26),
+    (-4.999999523162842, -164.375, 2295213638930577.0, 2.2952136e15),
+];
+
+#[test]
+fn gamma() {
+    check(&GAMMA, 6, Float::gamma, Float::gamma);
+}
+
+#[test]
+fn gamma_poles() {
+    assert_eq!(Float::gamma(0.0_f64), f64::INFINITY);
+    assert_eq!(Float::gamma(-0.0_f64), f64::NEG_INFINITY);
+    assert_eq!(Float::gamma(-0.0_f32), f32::NEG_INFINITY);
+    assert_eq!(Float::gamma(172.0_f64), f64::INFINITY);
+    assert_eq!(Float::gamma(f64::INFINITY), f64::INFINITY);
+
+    for x in [-1.0, -2.0, -171.0, -1e300, f64::NEG_INFINITY, f64::NAN] {
+        assert!(Float::gamma(x).is_nan());
+        assert!(Float::gamma(x as f32).is_nan());
+    }
+}
+
+#[test]
+fn ln_gamma() {
+    for &(x, want, want32, sign) in &LN_GAMMA {
+        let (v, s) = Float::ln_gamma(x);
+        assert_ulps_eq!(v, want, 6);
+        assert_eq!(s, sign);
+
+        let (v, s) = Float::ln_gamma(x as f32);
+        assert_ulps_eq!(v, want32, 1);
+        assert_eq!(s, sign);
+    }
+}
+
+#[test]
+fn ln_gamma_near_zeros() {
+    for &(x, want, want32, sign) in &LN_GAMMA_NEAR_ZERO {
+        let (v, s) = Float::ln_gamma(x);
+        assert_approx_eq!(v, want, abs = 3.0 * f64::EPSILON);
+        assert_eq!(s, sign);
+
+        let (v, s) = Float::ln_gamma(x as f32);
+        assert_ulps_eq!(v, want32, 1);
+        assert_eq!(s, sign);
+    }
+}
+
+#[test]
+fn ln_gamma_poles() {
+    assert_eq!(Float::ln_gamma(0.0_f64), (f64::INFINITY, 1));
+    assert_eq!(Float::ln_gamma(-0.0_f64), (f64::INFINITY, -1));
+    assert_eq!(Float::ln_gamma(-3.0_f64), (f64::INFINITY, 1));
+    assert_eq!(Float::ln_gamma(-3.0_f32), (f32::INFINITY, 1));
+    assert_eq!(Float::ln_gamma(f64::INFINITY), (f64::INFINITY, 1));
+    assert_eq!(Float::ln_gamma(1.0_f64), (0.0, 1));
+    assert_eq!(Float::ln_gamma(2.0_f32), (0.0, 1));
+}
+
+#[test]
+fn digamma() {
+    check(&DIGAMMA, 3, Float::digamma, Float::digamma);
+}
+
+#[test]
+fn digamma_near_zeros() {
+    for &(x, want, want32) in &DIGAMMA_NEAR_ZERO {
+        let abs = 3.0 * f64::EPSILON * (2.0 - x).ln();
+        assert_approx_eq!(Float::digamma(x), want, abs = abs);
+        assert_ulps_eq!(Float::digamma(x as f32), want32, 1);
+    }
+}
+
+#[test]
+fn digamma_poles() {
+    assert_eq!(Float::digamma(0.0_f64), f64::NEG_INFINITY);
+    assert_eq!(Float::digamma(-0.0_f32), f32::INFINITY);
+    assert_eq!(Float::digamma(f64::INFINITY), f64::INFINITY);
+
+    for x in [-1.0, -4.0, -1e300, f64::NEG_INFINITY, f64::NAN] {
+        assert!(Float::digamma(x).is_nan());
+        assert!(Float::digamma(x as f32).is_nan());
+    }
+}
+
+#[test]
+fn beta() {
+    for &(a, b, want, want32) in &BETA {
+        let ulps = if a < 0.0 || b < 0.0 { 20 } else { 16 };
+        assert_ulps_eq!(Float::beta(a, b), want, ulps);
+        assert_ulps_eq!(Float::beta(b, a), want, ulps);
+        assert_ulps_eq!(Float::beta(a as f32, b as f32), want32, 1);
+    }
+}
+
+#[test]
+fn beta_special_values() {
+    assert_eq!(Float::beta(0.0_f64, 1.0), f64::INFINITY);
+    assert_eq!(Float::beta(-0.5_f64, 0.5), 0.0);
+    assert_eq!(Float::beta(-2.5_f32, 1.5), 0.0);
+    assert_eq!(Float::beta(1.0_f64, f64::INFINITY), 0.0);
+    assert!(Float::beta(f64::NAN, 1.0).is_nan());
+    assert!(Float::beta(-1.0_f64, 2.0).is_nan());
+}
+
+#[test]
+fn erf() {
+    check(&ERF, 2, Float::erf, Float::erf);
+
+    assert_eq!(Float::erf(-0.0_f64).to_bits(), (-0.0_f64).to_bits());
+    assert_eq!(Float::erf(6.0_f64), 1.0);
+    assert_eq!(Float::erf(f32::NEG_INFINITY), -1.0);
+    assert!(Float::erf(f64::NAN).is_nan());
+}
+
+#[test]
+fn erfc() {
+    check(&ERFC, 4, Float::erfc, Float::erfc);
+
+    assert_eq!(Float::erfc(28.0_f64), 0.0);
+    assert_eq!(Float::erfc(f64::INFINITY), 0.0);
+    assert_eq!(Float::erfc(11.0_f32), 0.0);
+    assert_eq!(Float::erfc(f32::NEG_INFINITY), 2.0);
+    assert!(Float::erfc(f64::NAN).is_nan());
+}
+
+#[test]
+fn erf_inv() {
+    check(&ERF_INV, 2, Float::erf_inv, Float::erf_inv);
+
+    assert_eq!(Float::erf_inv(1.0_f64), f64::INFINITY);
+    assert_eq!(Float::erf_inv(-1.0_f32), f32::NEG_INFINITY);
+    assert_eq!(Float::erf_inv(-0.0_f64).to_bits(), (-0.0_f64).to_bits());
+
+    for y in [1.5, -1.0000001, f64::INFINITY, f64::NAN] {
+        assert!(Float::erf_inv(y).is_nan());
+        assert!(Float::erf_inv(y as f32).is_nan());
+    }
+}
+
+#[test]
+fn erf_inv_near_one() {
+    // The inputs next to 1 are exactly representable, so the results must
+    // still round-trip through `erfc`.
+    let tolerance = Tolerance::exact().rel(1e-14);
+    for k in 1..=50 {
+        let y = 1.0 - f64::EPSILON / 2.0 * f64::from(k);
+        let x = Float::erf_inv(y);
+        assert!(tolerance.accepts(Float::erfc(x), 1.0 - y));
+        assert!(tolerance.accepts(-Float::erf_inv(-y), x));
+    }
+}
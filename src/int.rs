@@ -1,7 +1,22 @@
+mod bit_indices;
+mod factor;
+mod from_int_error;
+#[allow(clippy::module_inception)]
 mod int;
+mod montgomery;
+mod morton;
+mod prime;
+mod radix;
 mod signed_int;
+mod subsets;
 mod unsigned_int;
 
+pub use bit_indices::BitIndices;
+pub use factor::{Divisors, Factorization};
+pub use from_int_error::FromIntError;
 pub use int::Int;
+pub use montgomery::Montgomery;
+pub use radix::{RadixBuf, RadixDisplay, RadixOptions};
 pub use signed_int::SignedInt;
+pub use subsets::{Submasks, Subsets};
 pub use unsigned_int::UnsignedInt;
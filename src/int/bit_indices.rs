@@ -0,0 +1,78 @@
+use std::iter::FusedIterator;
+
+use crate::Int;
+
+/// An iterator over the indices of the set bits of an `Int`, in ascending
+/// order, or descending when iterated from the back.
+///
+/// See [`Int::iter_ones`] and [`Int::iter_zeros`].
+///
+/// [`Int::iter_ones`]: crate::Int::iter_ones
+/// [`Int::iter_zeros`]: crate::Int::iter_zeros
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BitIndices<T: Int> {
+    /// The bits not yet yielded.
+    bits: T,
+}
+
+impl<T: Int> BitIndices<T> {
+    pub(crate) fn new(bits: T) -> Self {
+        Self { bits }
+    }
+}
+
+impl<T: Int> Iterator for BitIndices<T> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<u32> {
+        if self.bits == T::ZERO {
+            return None;
+        }
+
+        // Clear the lowest set bit, wrapping as `MIN - 1` overflows.
+        let i = self.bits.trailing_zeros();
+        self.bits &= self.bits.wrapping_sub(T::ONE);
+
+        Some(i)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<u32> {
+        self.next_back()
+    }
+}
+
+impl<T: Int> DoubleEndedIterator for BitIndices<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<u32> {
+        if self.bits == T::ZERO {
+            return None;
+        }
+
+        let i = T::BITS - 1 - self.bits.leading_zeros();
+        self.bits ^= T::ONE << i;
+
+        Some(i)
+    }
+}
+
+impl<T: Int> ExactSizeIterator for BitIndices<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+}
+
+impl<T: Int> FusedIterator for BitIndices<T> {}
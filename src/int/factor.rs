@@ -0,0 +1,257 @@
+use std::{fmt, iter::FusedIterator, ops::Deref};
+
+use crate::{int::prime::SMALL_PRIMES, Montgomery, Num, UnsignedInt};
+
+/// The most distinct primes any `UnsignedInt` can have, as the product of the
+/// first 26 primes is the largest primorial below `2^128`.
+///
+/// This is fixed at the `u128` maximum for every type, as array lengths
+/// cannot depend on `T::BITS` in a generic struct.
+const CAPACITY: usize = 26;
+
+/// The prime factorization of an `UnsignedInt`, as distinct primes in
+/// ascending order with their multiplicities.
+///
+/// Dereferences to a slice of `(prime, exponent)` pairs. Zero and one have
+/// no prime factors.
+///
+/// Room is deliberately kept for `CAPACITY` pairs whatever the width, so
+/// narrow types carry unused slots: a `u8` has at most 3 distinct primes.
+///
+/// See [`UnsignedInt::factorize`].
+///
+/// [`UnsignedInt::factorize`]: crate::UnsignedInt::factorize
+#[derive(Clone, Copy)]
+pub struct Factorization<T: UnsignedInt> {
+    factors: [(T, u32); CAPACITY],
+    len: usize,
+}
+
+impl<T: UnsignedInt> Factorization<T> {
+    /// The most distinct primes any `UnsignedInt` can have, which is that of
+    /// `u128` for every type.
+    pub const CAPACITY: usize = CAPACITY;
+
+    /// Returns the `(prime, exponent)` pairs as a slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[(T, u32)] {
+        &self.factors[..self.len]
+    }
+
+    /// Factors `n` with trial division and Pollard's rho.
+    pub(crate) fn new(n: T) -> Self {
+        let mut f = Self {
+            factors: [(T::ZERO, 0); CAPACITY],
+            len: 0,
+        };
+
+        if n == T::ZERO {
+            return f;
+        }
+
+        let mut n = n;
+        for p in SMALL_PRIMES {
+            let p = p.cast::<T>();
+            let mut e = 0;
+            while n % p == T::ZERO {
+                n /= p;
+                e += 1;
+            }
+
+            if e > 0 {
+                f.push(p, e);
+            }
+        }
+
+        f.split(n, 1);
+        f
+    }
+
+    /// Adds the prime factors of `n`, which has none in `SMALL_PRIMES`, each
+    /// `e` times.
+    fn split(&mut self, n: T, e: u32) {
+        if n == T::ONE {
+            return;
+        } else if n.is_prime() {
+            self.push(n, e);
+            return;
+        }
+
+        // Pollard's rho is slowest on squares of large primes, so take the
+        // root out first.
+        let root = n.isqrt();
+        if root * root == n {
+            self.split(root, e * 2);
+            return;
+        }
+
+        let d = find_factor(n);
+        self.split(d, e);
+        self.split(n / d, e);
+    }
+
+    /// Adds the prime `p` with exponent `e`, keeping the primes sorted.
+    fn push(&mut self, p: T, e: u32) {
+        let i = self.as_slice().partition_point(|&(q, _)| q < p);
+
+        if i < self.len && self.factors[i].0 == p {
+            self.factors[i].1 += e;
+        } else {
+            self.factors.copy_within(i..self.len, i + 1);
+            self.factors[i] = (p, e);
+            self.len += 1;
+        }
+    }
+}
+
+impl<T: UnsignedInt> Deref for Factorization<T> {
+    type Target = [(T, u32)];
+
+    fn deref(&self) -> &[(T, u32)] {
+        self.as_slice()
+    }
+}
+
+impl<T: UnsignedInt> AsRef<[(T, u32)]> for Factorization<T> {
+    fn as_ref(&self) -> &[(T, u32)] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T: UnsignedInt> IntoIterator for &'a Factorization<T> {
+    type Item = &'a (T, u32);
+    type IntoIter = std::slice::Iter<'a, (T, u32)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: UnsignedInt + fmt::Debug> fmt::Debug for Factorization<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+/// Returns a non-trivial factor of the odd composite `n`, which is not a
+/// perfect square.
+fn find_factor<T: UnsignedInt>(n: T) -> T {
+    // Wider types are slower, so split small values in `u64`.
+    if T::BITS > u64::BITS && n.cast::<u128>() <= u64::MAX.into() {
+        return find_factor(n.cast::<u64>()).cast();
+    }
+
+    // Rho fails for some constants, so try others until one works.
+    let mut c = T::ONE;
+    loop {
+        if let Some(d) = pollard_brent(n, c) {
+            return d;
+        }
+
+        c += T::ONE;
+    }
+}
+
+/// Brent's variant of Pollard's rho with the sequence `x^2 + c`, batching
+/// the differences into a product before each gcd.
+fn pollard_brent<T: UnsignedInt>(n: T, c: T) -> Option<T> {
+    const BATCH: u32 = 128;
+
+    let mont = Montgomery::new(n)?;
+    let c = mont.to_montgomery(c);
+    let f = |x: T| mont.add(mont.mul(x, x), c);
+
+    let (mut x, mut y, mut ys) = (T::ZERO, mont.to_montgomery(T::TWO), T::ZERO);
+    let mut q = mont.one();
+    let mut g = T::ONE;
+    let mut r = 1_u32;
+
+    // Multiplying by `R` does not change a gcd with the odd `n`, so the
+    // Montgomery forms can be used as they are.
+    while g == T::ONE {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+
+        let mut k = 0;
+        while k < r && g == T::ONE {
+            ys = y;
+            for _ in 0..BATCH.min(r - k) {
+                y = f(y);
+                q = mont.mul(q, mont.sub(x, y));
+            }
+
+            g = q.gcd(n);
+            k += BATCH;
+        }
+
+        r = r.checked_mul(2)?;
+    }
+
+    // The batch overshot to a multiple of `n`, so step through it again.
+    if g == n {
+        loop {
+            ys = f(ys);
+            g = mont.sub(x, ys).gcd(n);
+
+            if g != T::ONE {
+                break;
+            }
+        }
+    }
+
+    (g != n).then_some(g)
+}
+
+/// An iterator over the divisors of an `UnsignedInt`, starting with 1, in no
+/// particular order.
+///
+/// See [`UnsignedInt::divisors`].
+///
+/// [`UnsignedInt::divisors`]: crate::UnsignedInt::divisors
+#[derive(Clone, Copy)]
+pub struct Divisors<T: UnsignedInt> {
+    factors: Factorization<T>,
+
+    /// The exponent of each prime in `next`.
+    exponents: [u32; CAPACITY],
+    next: Option<T>,
+}
+
+impl<T: UnsignedInt> Divisors<T> {
+    pub(crate) fn new(n: T) -> Self {
+        Self {
+            factors: Factorization::new(n),
+            exponents: [0; CAPACITY],
+            next: (n != T::ZERO).then_some(T::ONE),
+        }
+    }
+}
+
+impl<T: UnsignedInt> Iterator for Divisors<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let d = self.next?;
+
+        // Count up through the exponents like an odometer, resetting each
+        // prime that has reached its multiplicity and carrying to the next.
+        let mut next = d;
+        self.next = None;
+        for (i, &(p, e)) in self.factors.iter().enumerate() {
+            if self.exponents[i] < e {
+                self.exponents[i] += 1;
+                self.next = Some(next * p);
+                break;
+            }
+
+            self.exponents[i] = 0;
+            next /= p.pow(e);
+        }
+
+        Some(d)
+    }
+}
+
+impl<T: UnsignedInt> FusedIterator for Divisors<T> {}
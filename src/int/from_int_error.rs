@@ -0,0 +1,26 @@
+use std::{error::Error, fmt};
+
+/// The error returned when converting between two `Int` types fails.
+///
+/// See [`Int::try_from_int`].
+///
+/// [`Int::try_from_int`]: crate::Int::try_from_int
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FromIntError {
+    /// The value is greater than the target type's `MinMax::MAX`.
+    Overflow,
+
+    /// The value is less than the target type's `MinMax::MIN`.
+    Underflow,
+}
+
+impl fmt::Display for FromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("integer is too large for the target type"),
+            Self::Underflow => f.write_str("integer is too small for the target type"),
+        }
+    }
+}
+
+impl Error for FromIntError {}
@@ -1,4 +1,9 @@
-use crate::{Bit, Num};
+use std::ops::{Bound, RangeBounds};
+
+use crate::{
+    Bit, BitIndices, FromIntError, Num, ParseNumError, RadixBuf, RadixDisplay, RadixOptions,
+    UnsignedInt,
+};
 
 /// Integer types.
 ///
@@ -13,6 +18,31 @@ use crate::{Bit, Num};
 /// [`i32`]: https://doc.rust-lang.org/std/primitive.i32.html
 /// [`u32`]: https://doc.rust-lang.org/std/primitive.u32.html
 pub trait Int: Eq + Ord + Bit + Num {
+    /// The byte array with the same size as this integer type.
+    ///
+    /// Used as the return type of `.to_be_bytes` and input for `.from_be_bytes`
+    /// and the other endian functions.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy;
+
+    /// The unsigned integer type with the same size, which is `Self` for
+    /// unsigned types.
+    type Unsigned: UnsignedInt;
+
+    /// The integer type with twice the size and the same signedness.
+    ///
+    /// There is no wider primitive than `i128` and `u128`, so for them this is
+    /// `Self`, and arithmetic in it overflows just as in `Self`. Generic code
+    /// relying on the extra width should check `Self::Wide::BITS > Self::BITS`,
+    /// or use `widening_mul`, which is exact for every width.
+    type Wide: Int;
+
+    /// The integer type with half the size and the same signedness.
+    ///
+    /// There is no narrower primitive than `i8` and `u8`, so for them this is
+    /// `Self`, and values do not necessarily fit in half the bits. Generic
+    /// code should check `Self::Narrow::BITS < Self::BITS`.
+    type Narrow: Int;
+
     /// The size of this integer type in bits.
     const BITS: u32;
 
@@ -72,6 +102,30 @@ pub trait Int: Eq + Ord + Bit + Num {
     #[must_use]
     fn to_le(self) -> Self;
 
+    /// Returns the memory representation as a big-endian byte array.
+    #[must_use]
+    fn to_be_bytes(self) -> Self::Bytes;
+
+    /// Returns the memory representation as a little-endian byte array.
+    #[must_use]
+    fn to_le_bytes(self) -> Self::Bytes;
+
+    /// Returns the memory representation as a native-endian byte array.
+    #[must_use]
+    fn to_ne_bytes(self) -> Self::Bytes;
+
+    /// Creates an integer from a big-endian byte array.
+    #[must_use]
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Creates an integer from a little-endian byte array.
+    #[must_use]
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Creates an integer from a native-endian byte array.
+    #[must_use]
+    fn from_ne_bytes(bytes: Self::Bytes) -> Self;
+
     /// Checked integer addition.
     #[must_use]
     fn checked_add(self, rhs: Self) -> Option<Self>;
@@ -255,12 +309,400 @@ pub trait Int: Eq + Ord + Bit + Num {
     /// Checked base 10 logarithm.
     #[must_use]
     fn checked_ilog10(self) -> Option<u32>;
+
+    /// Parses an integer from a string in the given base.
+    ///
+    /// The string may start with a `+` or `-` sign followed by digits, where
+    /// digits above 9 are the letters `a` to `z` in either case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseNumError>;
+
+    /// Converts an integer of any type to `Self`, failing if it is out of range.
+    ///
+    /// Returns `FromIntError::Overflow` if `x` is greater than `Self::MAX` and
+    /// `FromIntError::Underflow` if `x` is less than `Self::MIN`.
+    #[inline]
+    fn try_from_int<U: Int>(x: U) -> Result<Self, FromIntError> {
+        // Every negative value fits in `i128` and every non-negative one in `u128`.
+        if x < U::ZERO {
+            if x.cast::<i128>() < Self::MIN.cast::<i128>() {
+                return Err(FromIntError::Underflow);
+            }
+        } else if x.cast::<u128>() > Self::MAX.cast::<u128>() {
+            return Err(FromIntError::Overflow);
+        }
+
+        Ok(x.cast())
+    }
+
+    /// Converts an integer of any type to `Self`, clamping it to `Self`'s range.
+    #[must_use]
+    #[inline]
+    fn saturating_from_int<U: Int>(x: U) -> Self {
+        match Self::try_from_int(x) {
+            Ok(v) => v,
+            Err(FromIntError::Overflow) => Self::MAX,
+            Err(FromIntError::Underflow) => Self::MIN,
+        }
+    }
+
+    /// Converts an integer of any type to `Self`, truncating any high-order bits.
+    ///
+    /// This is the same as `x as Self`.
+    #[must_use]
+    #[inline(always)]
+    fn wrapping_from_int<U: Int>(x: U) -> Self {
+        x.cast()
+    }
+
+    /// Calculates the complete product `self * rhs` without overflow.
+    ///
+    /// Returns the low-order bits of the product and the high-order bits,
+    /// which together make up the double-width result. As with the inherent
+    /// method on the primitives, the low-order bits are always unsigned. For
+    /// `i128` and `u128` the product is computed in software.
+    #[must_use]
+    #[inline]
+    fn widening_mul(self, rhs: Self) -> (Self::Unsigned, Self) {
+        if Self::Wide::BITS > Self::BITS {
+            let p = self.cast::<Self::Wide>() * rhs.cast::<Self::Wide>();
+            return (p.cast(), (p >> Self::BITS).cast());
+        }
+
+        let (lo, mut hi) = widening_mul_u128(self.cast(), rhs.cast());
+
+        // A negative factor was taken as `2^128` more than it is, so the
+        // unsigned product is `2^128` times the other factor too large.
+        if self < Self::ZERO {
+            hi = hi.wrapping_sub(rhs.cast());
+        }
+        if rhs < Self::ZERO {
+            hi = hi.wrapping_sub(self.cast());
+        }
+
+        (lo.cast(), hi.cast())
+    }
+
+    /// Returns the `n`-th root of `self`, rounded toward zero.
+    ///
+    /// Exact for every width, as a float estimate is corrected with integer
+    /// arithmetic. Negative values only have odd roots, which are negative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, or if `self` is negative and `n` is even.
+    #[must_use]
+    #[inline]
+    fn iroot(self, n: u32) -> Self {
+        match self.checked_iroot(n) {
+            Some(r) => r,
+            None if n == 0 => panic!("attempt to calculate the zeroth root"),
+            None => panic!("argument of integer root must be non-negative for even roots"),
+        }
+    }
+
+    /// Checked integer `n`-th root, returning `None` if `n` is zero, or if
+    /// `self` is negative and `n` is even.
+    #[must_use]
+    fn checked_iroot(self, n: u32) -> Option<Self> {
+        if n == 0 || (self < Self::ZERO && n.is_multiple_of(2)) {
+            return None;
+        } else if self >= Self::ZERO || n == 1 {
+            return Some(iroot_non_negative(self, n));
+        }
+
+        // `-self` overflows for `MIN`, so take the root of `-(self + 1)`,
+        // then check whether one more is exact, which the odd power of the
+        // negated root can show without overflow.
+        let r = Self::ZERO - iroot_non_negative(Self::ZERO - (self + Self::ONE), n);
+        match (r - Self::ONE).checked_pow(n) {
+            Some(p) if p >= self => Some(r - Self::ONE),
+            _ => Some(r),
+        }
+    }
+
+    /// Returns whether `self` is the square of an integer.
+    ///
+    /// Negative values never are.
+    #[must_use]
+    #[inline]
+    fn is_perfect_square(self) -> bool {
+        // Squares are 0, 1, 4 or 9 modulo 16, which rules out most values
+        // before taking the root.
+        if self < Self::ZERO || !matches!((self & 15_u8.cast()).cast::<u8>(), 0 | 1 | 4 | 9) {
+            return false;
+        }
+
+        let root = self.isqrt();
+        root * root == self
+    }
+
+    /// Returns the base and exponent of `self` as a power with the largest
+    /// exponent above 1, or `None` if it is not such a power.
+    ///
+    /// Zero, one and negative one are powers with infinitely many exponents,
+    /// so also return `None`. Negative values are odd powers of negative
+    /// bases.
+    #[must_use]
+    fn perfect_power(self) -> Option<(Self, u32)> {
+        if self == Self::ZERO || self == Self::ONE {
+            return None;
+        }
+
+        // The exponent is at most the base 2 logarithm of the magnitude.
+        let max = if self < Self::ZERO {
+            Self::BITS - self.leading_ones()
+        } else {
+            self.ilog2()
+        };
+
+        (2..=max)
+            .rev()
+            .filter(|&k| self > Self::ZERO || k % 2 == 1)
+            .find_map(|k| {
+                let root = self.iroot(k);
+                (root.checked_pow(k) == Some(self)).then_some((root, k))
+            })
+    }
+
+    /// Returns whether bit `i` of `self` is set, counting from the least
+    /// significant bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than `BITS`.
+    #[must_use]
+    #[inline]
+    fn bit(self, i: u32) -> bool {
+        assert!(i < Self::BITS, "bit index out of range");
+
+        (self >> i) & Self::ONE == Self::ONE
+    }
+
+    /// Returns `self` with bit `i` set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than `BITS`.
+    #[must_use]
+    #[inline]
+    fn with_bit(self, i: u32) -> Self {
+        assert!(i < Self::BITS, "bit index out of range");
+
+        self | (Self::ONE << i)
+    }
+
+    /// Returns `self` with bit `i` cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than `BITS`.
+    #[must_use]
+    #[inline]
+    fn clear_bit(self, i: u32) -> Self {
+        assert!(i < Self::BITS, "bit index out of range");
+
+        self & !(Self::ONE << i)
+    }
+
+    /// Returns `self` with bit `i` flipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than `BITS`.
+    #[must_use]
+    #[inline]
+    fn toggle_bit(self, i: u32) -> Self {
+        assert!(i < Self::BITS, "bit index out of range");
+
+        self ^ (Self::ONE << i)
+    }
+
+    /// Returns the `len` bits of `self` starting at bit `start`, moved down to
+    /// the least significant bits.
+    ///
+    /// The bits are zero-extended, even for signed types.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start + len` is greater than `BITS`.
+    #[must_use]
+    #[inline]
+    fn extract_bits(self, start: u32, len: u32) -> Self {
+        assert_bit_field::<Self>(start, len);
+
+        self.checked_shr(start).unwrap_or(Self::ZERO) & Self::low_mask(len)
+    }
+
+    /// Returns `self` with the `len` bits starting at bit `start` replaced by
+    /// the lowest `len` bits of `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start + len` is greater than `BITS`.
+    #[must_use]
+    #[inline]
+    fn insert_bits(self, start: u32, len: u32, value: Self) -> Self {
+        assert_bit_field::<Self>(start, len);
+
+        // Shifting by `BITS` overflows, but only happens for empty fields.
+        let mask = Self::low_mask(len).checked_shl(start).unwrap_or(Self::ZERO);
+        let value = value.checked_shl(start).unwrap_or(Self::ZERO);
+
+        (self & !mask) | (value & mask)
+    }
+
+    /// Returns a value with the lowest `n` bits set, which is every bit if
+    /// `n` is `BITS`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `BITS`.
+    #[must_use]
+    #[inline]
+    fn low_mask(n: u32) -> Self {
+        assert!(n <= Self::BITS, "mask length out of range");
+
+        !(!Self::ZERO).checked_shl(n).unwrap_or(Self::ZERO)
+    }
+
+    /// Returns a value with the bits in `range` set, such as `4..8` for the
+    /// second lowest nibble.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts after it ends or ends after `BITS`.
+    #[must_use]
+    #[inline]
+    fn mask(range: impl RangeBounds<u32>) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => Self::BITS,
+        };
+        assert!(start <= end, "mask range starts after it ends");
+
+        Self::low_mask(end) ^ Self::low_mask(start)
+    }
+
+    /// Returns an iterator over the indices of the set bits of `self`, in
+    /// ascending order, or descending from the back.
+    #[must_use]
+    #[inline(always)]
+    fn iter_ones(self) -> BitIndices<Self> {
+        BitIndices::new(self)
+    }
+
+    /// Returns an iterator over the indices of the clear bits of `self`, in
+    /// ascending order, or descending from the back.
+    #[must_use]
+    #[inline(always)]
+    fn iter_zeros(self) -> BitIndices<Self> {
+        BitIndices::new(!self)
+    }
+
+    /// Formats `self` in the radix given by `options` into a stack buffer.
+    #[must_use]
+    #[inline]
+    fn to_radix(self, options: RadixOptions) -> RadixBuf {
+        RadixBuf::new(self, options)
+    }
+
+    /// Formats `self` in the radix given by `options` into the start of `buf`.
+    ///
+    /// Returns the number of bytes written, or `None` if `buf` is too small,
+    /// in which case its contents are unspecified.
+    #[inline]
+    fn write_radix(self, buf: &mut [u8], options: RadixOptions) -> Option<usize> {
+        let s = RadixBuf::new(self, options);
+        let s = s.as_bytes();
+        buf.get_mut(..s.len())?.copy_from_slice(s);
+
+        Some(s.len())
+    }
+
+    /// Returns an adapter that formats `self` in the radix given by `options`
+    /// with `write!` and the other `core::fmt` macros.
+    #[must_use]
+    #[inline(always)]
+    fn display_radix(self, options: RadixOptions) -> RadixDisplay<Self> {
+        RadixDisplay::new(self, options)
+    }
+}
+
+/// Panics if the `len` bits starting at bit `start` do not fit in `T`.
+#[inline]
+fn assert_bit_field<T: Int>(start: u32, len: u32) {
+    assert!(
+        start <= T::BITS && len <= T::BITS - start,
+        "bit field out of range"
+    );
+}
+
+/// Returns the `n`-th root of the non-negative `x`, rounded down.
+fn iroot_non_negative<T: Int>(x: T, n: u32) -> T {
+    if n == 1 {
+        return x;
+    } else if n == 2 {
+        return x.isqrt();
+    }
+
+    // Values below `2^n` have a root of zero or one.
+    if x.checked_ilog2().is_none_or(|log| log < n) {
+        return if x == T::ZERO { T::ZERO } else { T::ONE };
+    }
+
+    // The root has at most a third of the bits, so the float estimate is
+    // off by at most one or two and can be stepped to the exact value.
+    let mut root = x.cast::<f64>().powf(1.0 / f64::from(n)).cast::<T>();
+    while root.checked_pow(n).is_none_or(|p| p > x) {
+        root -= T::ONE;
+    }
+    while (root + T::ONE).checked_pow(n).is_some_and(|p| p <= x) {
+        root += T::ONE;
+    }
+
+    root
+}
+
+/// Returns the low and high halves of the 256-bit product `a * b`.
+fn widening_mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+
+    let (a_lo, a_hi) = (a & MASK, a >> 64);
+    let (b_lo, b_hi) = (b & MASK, b >> 64);
+
+    let ll = a_lo * b_lo;
+    let lh = a_lo * b_hi;
+    let hl = a_hi * b_lo;
+    let hh = a_hi * b_hi;
+
+    // The middle 64-bit column, whose carry goes to the high half.
+    let mid = (ll >> 64) + (lh & MASK) + (hl & MASK);
+
+    let lo = (ll & MASK) | (mid << 64);
+    let hi = hh + (lh >> 64) + (hl >> 64) + (mid >> 64);
+
+    (lo, hi)
 }
 
 macro_rules! impl_integer {
-    ($($t:ty )*) => {
+    ($($t:ty, $unsigned:ty, $wide:ty, $narrow:ty);* $(;)*) => {
         $(
             impl Int for $t {
+                type Bytes = [u8; std::mem::size_of::<Self>()];
+                type Unsigned = $unsigned;
+                type Wide = $wide;
+                type Narrow = $narrow;
+
                 const BITS: u32 = Self::BITS;
 
                 #[inline(always)]
@@ -333,6 +775,36 @@ macro_rules! impl_integer {
                     Self::to_le(self)
                 }
 
+                #[inline(always)]
+                fn to_be_bytes(self) -> Self::Bytes {
+                    Self::to_be_bytes(self)
+                }
+
+                #[inline(always)]
+                fn to_le_bytes(self) -> Self::Bytes {
+                    Self::to_le_bytes(self)
+                }
+
+                #[inline(always)]
+                fn to_ne_bytes(self) -> Self::Bytes {
+                    Self::to_ne_bytes(self)
+                }
+
+                #[inline(always)]
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    Self::from_be_bytes(bytes)
+                }
+
+                #[inline(always)]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    Self::from_le_bytes(bytes)
+                }
+
+                #[inline(always)]
+                fn from_ne_bytes(bytes: Self::Bytes) -> Self {
+                    Self::from_ne_bytes(bytes)
+                }
+
                 #[inline(always)]
                 fn checked_add(self, rhs: Self) -> Option<Self> {
                     Self::checked_add(self, rhs)
@@ -562,9 +1034,43 @@ macro_rules! impl_integer {
                 fn checked_ilog10(self) -> Option<u32> {
                     self.checked_ilog10()
                 }
+
+                #[inline(always)]
+                fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseNumError> {
+                    Self::from_str_radix(s, radix).map_err(ParseNumError::from)
+                }
             }
         )*
     };
 }
 
-impl_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+impl_integer!(
+    i8, u8, i16, i8;
+    i16, u16, i32, i8;
+    i32, u32, i64, i16;
+    i64, u64, i128, i32;
+    i128, u128, i128, i64;
+    u8, u8, u16, u8;
+    u16, u16, u32, u8;
+    u32, u32, u64, u16;
+    u64, u64, u128, u32;
+    u128, u128, u128, u64;
+);
+
+#[cfg(target_pointer_width = "16")]
+impl_integer!(
+    isize, usize, i32, i8;
+    usize, usize, u32, u8;
+);
+
+#[cfg(target_pointer_width = "32")]
+impl_integer!(
+    isize, usize, i64, i16;
+    usize, usize, u64, u16;
+);
+
+#[cfg(target_pointer_width = "64")]
+impl_integer!(
+    isize, usize, i128, i32;
+    usize, usize, u128, u32;
+);
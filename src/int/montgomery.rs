@@ -0,0 +1,152 @@
+use crate::UnsignedInt;
+
+/// Montgomery form arithmetic modulo a fixed odd modulus.
+///
+/// A value `x` is represented as `x * R mod m`, where `R` is `2^BITS`, which
+/// turns the division in each modular multiplication into multiplications
+/// and a shift. Converting into and out of the form costs a multiplication
+/// each, so this pays off for repeated operations such as exponentiation.
+///
+/// Every value taken and returned by the arithmetic methods is in Montgomery
+/// form and less than the modulus.
+///
+/// See [`UnsignedInt::mod_pow`], which uses this for odd moduli.
+///
+/// [`UnsignedInt::mod_pow`]: crate::UnsignedInt::mod_pow
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Montgomery<T: UnsignedInt> {
+    modulus: T,
+
+    /// The inverse of the modulus modulo `R`.
+    inv: T,
+
+    /// `R mod m`, the Montgomery form of one.
+    one: T,
+
+    /// `R^2 mod m`, used to convert into the form.
+    r2: T,
+}
+
+impl<T: UnsignedInt> Montgomery<T> {
+    /// Creates the context for arithmetic modulo `modulus`.
+    ///
+    /// Returns `None` if `modulus` is even, including zero, as it must be
+    /// coprime to `R`.
+    #[must_use]
+    pub fn new(modulus: T) -> Option<Self> {
+        if modulus & T::ONE == T::ZERO {
+            return None;
+        }
+
+        // Any odd `m` is its own inverse modulo 8, and each Newton step
+        // doubles the number of correct bits.
+        let mut inv = modulus;
+        let mut bits = 3;
+        while bits < T::BITS {
+            inv = inv.wrapping_mul(T::TWO.wrapping_sub(modulus.wrapping_mul(inv)));
+            bits *= 2;
+        }
+
+        // `R mod m` is one more than `(R - 1) mod m`, wrapping to zero.
+        let one = (T::MAX % modulus + T::ONE) % modulus;
+        let r2 = one.mod_mul(one, modulus);
+
+        Some(Self {
+            modulus,
+            inv,
+            one,
+            r2,
+        })
+    }
+
+    /// Returns the modulus.
+    #[must_use]
+    #[inline(always)]
+    pub fn modulus(&self) -> T {
+        self.modulus
+    }
+
+    /// Returns one in Montgomery form.
+    #[must_use]
+    #[inline(always)]
+    pub fn one(&self) -> T {
+        self.one
+    }
+
+    /// Converts `x` into Montgomery form, reducing it first.
+    #[must_use]
+    #[inline]
+    pub fn to_montgomery(&self, x: T) -> T {
+        self.mul(x % self.modulus, self.r2)
+    }
+
+    /// Converts `x` out of Montgomery form.
+    #[must_use]
+    #[inline]
+    pub fn from_montgomery(&self, x: T) -> T {
+        self.reduce(x, T::ZERO)
+    }
+
+    /// Calculates `a + b` modulo the modulus.
+    #[must_use]
+    #[inline]
+    pub fn add(&self, a: T, b: T) -> T {
+        let (sum, overflow) = a.overflowing_add(b);
+
+        if overflow || sum >= self.modulus {
+            sum.wrapping_sub(self.modulus)
+        } else {
+            sum
+        }
+    }
+
+    /// Calculates `a - b` modulo the modulus.
+    #[must_use]
+    #[inline]
+    pub fn sub(&self, a: T, b: T) -> T {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.modulus)
+        }
+    }
+
+    /// Calculates `a * b` modulo the modulus.
+    #[must_use]
+    #[inline]
+    pub fn mul(&self, a: T, b: T) -> T {
+        let (lo, hi) = a.widening_mul(b);
+        self.reduce(lo, hi)
+    }
+
+    /// Calculates `a^exp` modulo the modulus, by squaring.
+    #[must_use]
+    pub fn pow(&self, a: T, exp: T) -> T {
+        let mut base = a;
+        let mut exp = exp;
+        let mut acc = self.one;
+
+        while exp != T::ZERO {
+            if exp & T::ONE == T::ONE {
+                acc = self.mul(acc, base);
+            }
+
+            base = self.mul(base, base);
+            exp >>= 1_u32;
+        }
+
+        acc
+    }
+
+    /// Calculates `(hi * R + lo) / R` modulo the modulus, where `hi` is less
+    /// than the modulus.
+    #[inline]
+    fn reduce(&self, lo: T, hi: T) -> T {
+        // `q * m` has the same low half as the input, so subtracting it
+        // leaves an exact multiple of `R`.
+        let q = lo.wrapping_mul(self.inv);
+        let (_, qm) = q.widening_mul(self.modulus);
+
+        self.sub(hi, qm)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::UnsignedInt;
+
+/// Returns how many bits of each of `ways` coordinates of type `T` fit in a
+/// Morton code of type `W`.
+pub(crate) fn coordinate_bits<T: UnsignedInt, W: UnsignedInt>(ways: u32) -> u32 {
+    T::BITS.min(W::BITS / ways)
+}
+
+/// Spreads the low `bits` bits of `x` out so that bit `i` moves to bit
+/// `i * ways`, clearing the rest.
+pub(crate) fn spread<W: UnsignedInt>(x: W, ways: u32, bits: u32) -> W {
+    let mut x = x & W::low_mask(bits);
+
+    // Split the bits into ever smaller blocks, moving each upper half up
+    // until the blocks are single bits `ways` apart.
+    let mut block = bits.checked_next_power_of_two().unwrap_or(0) / 2;
+    while block > 0 {
+        x = (x | (x << ((ways - 1) * block))) & blocks(ways, block);
+        block /= 2;
+    }
+
+    x
+}
+
+/// Gathers every `ways`-th bit of `x`, starting from bit 0, into its low
+/// `bits` bits, undoing `spread`.
+pub(crate) fn compact<W: UnsignedInt>(x: W, ways: u32, bits: u32) -> W {
+    let mut x = x & blocks(ways, 1) & W::low_mask(ways * bits);
+
+    // Merge the single bits into ever larger blocks.
+    let mut block = 1;
+    while block < bits {
+        x = (x | (x >> ((ways - 1) * block))) & blocks(ways, block * 2);
+        block *= 2;
+    }
+
+    x & W::low_mask(bits)
+}
+
+/// Returns runs of `block` set bits, one every `ways * block` bits.
+fn blocks<W: UnsignedInt>(ways: u32, block: u32) -> W {
+    let mut mask = W::low_mask(block.min(W::BITS));
+    let mut step = ways * block;
+
+    while step < W::BITS {
+        mask = mask | (mask << step);
+        step *= 2;
+    }
+
+    mask
+}
@@ -0,0 +1,212 @@
+use crate::{Montgomery, Num, UnsignedInt};
+
+/// The primes used for trial division before any probable prime test.
+pub(crate) const SMALL_PRIMES: [u8; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// Miller-Rabin bases that are correct for every value below `2^32`.
+const BASES_32: [u64; 3] = [2, 7, 61];
+
+/// Miller-Rabin bases that are correct for every value below `2^64`.
+const BASES_64: [u64; 7] = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+
+/// Returns whether `n` is prime.
+///
+/// Values below `2^64` are tested deterministically with Miller-Rabin, and
+/// larger ones with the Baillie-PSW test, which has no known counterexample.
+pub(crate) fn is_prime<T: UnsignedInt>(n: T) -> bool {
+    // Compare in `u128` so the bounds are not truncated to narrow types.
+    let v = n.cast::<u128>();
+
+    // Wider types are slower, so test small values in `u64`.
+    if T::BITS > u64::BITS && v <= u64::MAX.into() {
+        return is_prime(n.cast::<u64>());
+    }
+
+    for p in SMALL_PRIMES {
+        let p = p.cast::<T>();
+        if n == p {
+            return true;
+        } else if n % p == T::ZERO {
+            return false;
+        }
+    }
+
+    // Anything left below the square of the next prime, 53, is prime.
+    if v < 2809 {
+        return v > 1;
+    }
+
+    let Some(mont) = Montgomery::new(n) else {
+        return false;
+    };
+
+    if v <= u32::MAX.into() {
+        BASES_32
+            .iter()
+            .all(|&a| is_strong_probable_prime(&mont, a.cast()))
+    } else if v <= u64::MAX.into() {
+        BASES_64
+            .iter()
+            .all(|&a| is_strong_probable_prime(&mont, a.cast()))
+    } else {
+        is_strong_probable_prime(&mont, T::TWO) && is_strong_lucas_probable_prime(&mont)
+    }
+}
+
+/// Returns whether the odd modulus passes the Miller-Rabin test to base `a`.
+fn is_strong_probable_prime<T: UnsignedInt>(mont: &Montgomery<T>, a: T) -> bool {
+    let n = mont.modulus();
+
+    // Bases that are multiples of `n` say nothing about it.
+    let a = a % n;
+    if a == T::ZERO {
+        return true;
+    }
+
+    // `n - 1 == d * 2^s` with `d` odd.
+    let s = (n - T::ONE).trailing_zeros();
+    let d = (n - T::ONE) >> s;
+
+    let one = mont.one();
+    let minus_one = mont.sub(T::ZERO, one);
+    let mut x = mont.pow(mont.to_montgomery(a), d);
+
+    if x == one || x == minus_one {
+        return true;
+    }
+
+    for _ in 1..s {
+        x = mont.mul(x, x);
+        if x == minus_one {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Returns whether the odd modulus, which is not a multiple of any small
+/// prime, passes the strong Lucas test with Selfridge's parameters.
+fn is_strong_lucas_probable_prime<T: UnsignedInt>(mont: &Montgomery<T>) -> bool {
+    let n = mont.modulus();
+
+    // Squares have no `D` with a Jacobi symbol of -1.
+    if n.is_perfect_square() {
+        return false;
+    }
+
+    // Find the first `D` in 5, -7, 9, -11, ... with `(D / n) == -1`.
+    let mut d = 5_u64;
+    let mut negative = false;
+    loop {
+        let dn = if negative {
+            n - d.cast::<T>() % n
+        } else {
+            d.cast::<T>() % n
+        };
+
+        match jacobi(dn, n) {
+            -1 => break,
+            0 if d.cast::<T>() != n => return false,
+            _ => {}
+        }
+
+        d += 2;
+        negative = !negative;
+    }
+
+    // `P == 1` and `Q == (1 - D) / 4`, all in Montgomery form.
+    let one = mont.one();
+    let d_mont = mont.to_montgomery(d.cast());
+    let d_mont = if negative {
+        mont.sub(T::ZERO, d_mont)
+    } else {
+        d_mont
+    };
+    let q_abs = mont.to_montgomery(((d + 1) / 4).cast());
+    let q = if negative {
+        q_abs
+    } else {
+        mont.sub(T::ZERO, q_abs)
+    };
+
+    // `n + 1 == k * 2^s` with `k` odd, where `n + 1` may overflow.
+    let (k, s) = if n == T::MAX {
+        (T::ONE, T::BITS)
+    } else {
+        let m = n + T::ONE;
+        (m >> m.trailing_zeros(), m.trailing_zeros())
+    };
+
+    // Compute `U_k`, `V_k` and `Q^k` from the most significant bit of `k`.
+    let (mut u, mut v, mut qk) = (one, one, q);
+    for i in (0..k.ilog2()).rev() {
+        // Double the index.
+        u = mont.mul(u, v);
+        v = mont.sub(mont.mul(v, v), mont.add(qk, qk));
+        qk = mont.mul(qk, qk);
+
+        if (k >> i) & T::ONE == T::ONE {
+            // Increment the index.
+            let (pu, dv) = (mont.add(u, v), mont.add(mont.mul(d_mont, u), v));
+            u = half(pu, n);
+            v = half(dv, n);
+            qk = mont.mul(qk, q);
+        }
+    }
+
+    if u == T::ZERO || v == T::ZERO {
+        return true;
+    }
+
+    for _ in 1..s {
+        v = mont.sub(mont.mul(v, v), mont.add(qk, qk));
+        qk = mont.mul(qk, qk);
+
+        if v == T::ZERO {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Returns `x / 2` modulo the odd `n`.
+fn half<T: UnsignedInt>(x: T, n: T) -> T {
+    if x & T::ONE == T::ZERO {
+        x >> 1_u32
+    } else {
+        // `(x + n) / 2` without overflow, as both are odd.
+        (x >> 1_u32) + (n >> 1_u32) + T::ONE
+    }
+}
+
+/// Returns the Jacobi symbol `(a / n)` for odd `n`.
+fn jacobi<T: UnsignedInt>(a: T, n: T) -> i32 {
+    let (mut a, mut n) = (a % n, n);
+    let mut result = 1;
+
+    while a != T::ZERO {
+        let zeros = a.trailing_zeros();
+        a >>= zeros;
+
+        // `(2 / n)` is -1 when `n` is 3 or 5 modulo 8.
+        let n8 = (n & 7_u8.cast()).cast::<u8>();
+        if zeros % 2 == 1 && (n8 == 3 || n8 == 5) {
+            result = -result;
+        }
+
+        // Quadratic reciprocity flips the sign when both are 3 modulo 4.
+        if (a & 3_u8.cast()) == 3_u8.cast() && n8 % 4 == 3 {
+            result = -result;
+        }
+
+        (a, n) = (n % a, a);
+    }
+
+    if n == T::ONE {
+        result
+    } else {
+        0
+    }
+}
@@ -0,0 +1,203 @@
+use std::{fmt, ops::Deref, str};
+
+use crate::Int;
+
+const LOWER_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+const UPPER_DIGITS: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Options for formatting an `Int` in an arbitrary radix.
+///
+/// See [`Int::to_radix`].
+///
+/// [`Int::to_radix`]: crate::Int::to_radix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RadixOptions {
+    radix: u32,
+    zero_pad: bool,
+    plus_sign: bool,
+    uppercase: bool,
+}
+
+impl RadixOptions {
+    /// Creates options for formatting in the given radix with no padding,
+    /// no `+` sign and lowercase digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    #[must_use]
+    pub const fn new(radix: u32) -> Self {
+        assert!(
+            radix >= 2 && radix <= 36,
+            "radix must be in the range from 2 to 36"
+        );
+
+        Self {
+            radix,
+            zero_pad: false,
+            plus_sign: false,
+            uppercase: false,
+        }
+    }
+
+    /// Sets whether to pad with leading zeros to the number of digits of the
+    /// type's largest magnitude.
+    #[must_use]
+    pub const fn zero_pad(mut self, zero_pad: bool) -> Self {
+        self.zero_pad = zero_pad;
+        self
+    }
+
+    /// Sets whether to write a `+` sign before non-negative values.
+    #[must_use]
+    pub const fn plus_sign(mut self, plus_sign: bool) -> Self {
+        self.plus_sign = plus_sign;
+        self
+    }
+
+    /// Sets whether to write digits above 9 as uppercase letters.
+    #[must_use]
+    pub const fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Returns the radix.
+    #[must_use]
+    pub const fn radix(&self) -> u32 {
+        self.radix
+    }
+}
+
+/// A stack buffer holding an `Int` formatted in some radix.
+///
+/// Dereferences to the formatted `str`.
+#[derive(Clone, Copy)]
+pub struct RadixBuf {
+    buf: [u8; RadixBuf::CAPACITY],
+    start: usize,
+}
+
+impl RadixBuf {
+    /// The maximum length of any formatted `Int`: a sign and 128 binary digits.
+    pub const CAPACITY: usize = 129;
+
+    /// Returns the formatted value as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only ASCII signs and digits are ever written to the buffer.
+        unsafe { str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    /// Returns the formatted value as a byte slice.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[self.start..]
+    }
+
+    pub(crate) fn new<T: Int>(value: T, options: RadixOptions) -> Self {
+        let digits = if options.uppercase {
+            UPPER_DIGITS
+        } else {
+            LOWER_DIGITS
+        };
+        let radix = T::cast_from(options.radix);
+        let negative = value < T::ZERO;
+
+        let mut buf = [b'0'; Self::CAPACITY];
+        let mut i = Self::CAPACITY;
+        let mut x = value;
+
+        // Negative values are divided as-is so `MIN` needs no special case;
+        // their remainders are never positive.
+        loop {
+            let r = x % radix;
+            let d = if negative { T::ZERO - r } else { r };
+            i -= 1;
+            buf[i] = digits[d.cast::<usize>()];
+            x /= radix;
+
+            if x == T::ZERO {
+                break;
+            }
+        }
+
+        if options.zero_pad {
+            i = Self::CAPACITY - max_digits::<T>(options.radix);
+        }
+
+        if negative {
+            i -= 1;
+            buf[i] = b'-';
+        } else if options.plus_sign {
+            i -= 1;
+            buf[i] = b'+';
+        }
+
+        Self { buf, start: i }
+    }
+}
+
+/// Returns the number of digits of the largest magnitude of `T` in `radix`.
+fn max_digits<T: Int>(radix: u32) -> usize {
+    let signed = T::MIN < T::ZERO;
+    let max = T::MAX.cast::<u128>() + u128::from(signed);
+
+    max.ilog(u128::from(radix)) as usize + 1
+}
+
+impl Deref for RadixBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for RadixBuf {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for RadixBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for RadixBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for RadixBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// A `core::fmt` adapter that formats an `Int` in some radix.
+///
+/// See [`Int::display_radix`].
+///
+/// [`Int::display_radix`]: crate::Int::display_radix
+#[derive(Clone, Copy, Debug)]
+pub struct RadixDisplay<T: Int> {
+    value: T,
+    options: RadixOptions,
+}
+
+impl<T: Int> RadixDisplay<T> {
+    pub(crate) fn new(value: T, options: RadixOptions) -> Self {
+        Self { value, options }
+    }
+}
+
+impl<T: Int> fmt::Display for RadixDisplay<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        RadixBuf::new(self.value, self.options).fmt(f)
+    }
+}
@@ -3,7 +3,7 @@ use crate::{Int, Signed, UnsignedInt};
 /// Signed integers.
 pub trait SignedInt: Int + Signed {
     /// The unsigned integer type with the same size.
-    type U: UnsignedInt;
+    type U: UnsignedInt<S = Self>;
 
     /// Checked absolute value.
     #[must_use]
@@ -120,6 +120,60 @@ pub trait SignedInt: Int + Signed {
     /// Checked base 10 logarithm.
     #[must_use]
     fn checked_ilog10(self) -> Option<u32>;
+
+    /// Calculates the greatest common divisor of the magnitudes of `self` and
+    /// `other`.
+    ///
+    /// Returned as the unsigned type, as the result for `MIN` and zero,
+    /// `2^(BITS - 1)`, does not fit in `Self`.
+    #[must_use]
+    #[inline]
+    fn gcd(self, other: Self) -> Self::U {
+        self.unsigned_abs().gcd(other.unsigned_abs())
+    }
+
+    /// Calculates the least common multiple of the magnitudes of `self` and
+    /// `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result overflows the unsigned type.
+    #[must_use]
+    #[inline]
+    fn lcm(self, other: Self) -> Self::U {
+        self.unsigned_abs().lcm(other.unsigned_abs())
+    }
+
+    /// Checked least common multiple of the magnitudes of `self` and `other`,
+    /// returning `None` if the result overflows the unsigned type.
+    #[must_use]
+    #[inline]
+    fn checked_lcm(self, other: Self) -> Option<Self::U> {
+        self.unsigned_abs().checked_lcm(other.unsigned_abs())
+    }
+
+    /// Calculates the greatest common divisor of `self` and `other` along with
+    /// Bézout coefficients `x` and `y` such that `self * x + other * y == gcd`.
+    ///
+    /// See `UnsignedInt::extended_gcd`, which this applies to the magnitudes
+    /// before giving the coefficients the signs of their operands.
+    #[must_use]
+    #[inline]
+    fn extended_gcd(self, other: Self) -> (Self::U, Self, Self) {
+        let (g, x, y) = self.unsigned_abs().extended_gcd(other.unsigned_abs());
+        let x = if self < Self::ZERO {
+            x.wrapping_neg()
+        } else {
+            x
+        };
+        let y = if other < Self::ZERO {
+            y.wrapping_neg()
+        } else {
+            y
+        };
+
+        (g, x, y)
+    }
 }
 
 macro_rules! impl_signed_integer {
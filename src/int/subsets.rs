@@ -0,0 +1,97 @@
+use std::iter::FusedIterator;
+
+use crate::UnsignedInt;
+
+/// An iterator over the submasks of an `UnsignedInt`, from the mask itself
+/// down to zero.
+///
+/// See [`UnsignedInt::submasks`].
+///
+/// [`UnsignedInt::submasks`]: crate::UnsignedInt::submasks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Submasks<T: UnsignedInt> {
+    mask: T,
+    next: Option<T>,
+}
+
+impl<T: UnsignedInt> Submasks<T> {
+    pub(crate) fn new(mask: T) -> Self {
+        Self {
+            mask,
+            next: Some(mask),
+        }
+    }
+}
+
+impl<T: UnsignedInt> Iterator for Submasks<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let s = self.next?;
+
+        // Subtracting one borrows through the clear bits of the mask, which
+        // the `&` then drops, giving the next smaller submask.
+        self.next = (s != T::ZERO).then(|| (s - T::ONE) & self.mask);
+
+        Some(s)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The submasks left are numbered by the bits of `next` in the mask,
+        // which overflows `usize` for every submask of a wide mask.
+        let Some(s) = self.next else {
+            return (0, Some(0));
+        };
+        let len = s.pext(self.mask).cast::<u128>().checked_add(1);
+        let len = len.and_then(|len| usize::try_from(len).ok());
+
+        (len.unwrap_or(usize::MAX), len)
+    }
+}
+
+impl<T: UnsignedInt> FusedIterator for Submasks<T> {}
+
+/// An iterator over the values with a fixed number of the lowest bits of an
+/// `UnsignedInt` set, in ascending order.
+///
+/// See [`UnsignedInt::subsets`].
+///
+/// [`UnsignedInt::subsets`]: crate::UnsignedInt::subsets
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Subsets<T: UnsignedInt> {
+    /// The largest value yielded, with the highest bits set.
+    last: T,
+    next: Option<T>,
+}
+
+impl<T: UnsignedInt> Subsets<T> {
+    pub(crate) fn new(n: u32, k: u32) -> Self {
+        assert!(n <= T::BITS, "subset bit count out of range");
+        assert!(k <= n, "subset size is greater than the bit count");
+
+        // Shifting by `BITS` overflows, but only happens for empty subsets.
+        Self {
+            last: T::low_mask(k).checked_shl(n - k).unwrap_or(T::ZERO),
+            next: Some(T::low_mask(k)),
+        }
+    }
+}
+
+impl<T: UnsignedInt> Iterator for Subsets<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let s = self.next?;
+        self.next = if s == self.last {
+            None
+        } else {
+            s.next_same_popcount()
+        };
+
+        Some(s)
+    }
+}
+
+impl<T: UnsignedInt> FusedIterator for Subsets<T> {}
@@ -1,7 +1,10 @@
-use crate::{Int, SignedInt};
+use crate::{
+    int::{morton, prime},
+    Divisors, Factorization, Int, Montgomery, Num, SignedInt, Submasks, Subsets,
+};
 
 /// Unsigned integers.
-pub trait UnsignedInt: Int {
+pub trait UnsignedInt: Int<Unsigned = Self> {
     /// The signed integer type with the same size.
     type S: SignedInt;
 
@@ -40,6 +43,657 @@ pub trait UnsignedInt: Int {
     /// Reinterpret bits as the signed integer of the same size.
     #[must_use]
     fn cast_signed(self) -> Self::S;
+
+    /// Returns `true` if and only if `self == 2^k` for some `k`.
+    #[must_use]
+    fn is_power_of_two(self) -> bool;
+
+    /// Returns the smallest power of two greater than or equal to `self`.
+    ///
+    /// When the return value overflows, it panics in debug mode and the return
+    /// value is wrapped to 0 in release mode.
+    #[must_use]
+    fn next_power_of_two(self) -> Self;
+
+    /// Returns the smallest power of two greater than or equal to `self`. If
+    /// the next power of two is greater than the type's maximum value, `None`
+    /// is returned.
+    #[must_use]
+    fn checked_next_power_of_two(self) -> Option<Self>;
+
+    /// Calculates the quotient of `self` and `rhs`, rounding the result towards
+    /// positive infinity.
+    #[must_use]
+    fn div_ceil(self, rhs: Self) -> Self;
+
+    /// Calculates the smallest value greater than or equal to `self` that is a
+    /// multiple of `rhs`.
+    #[must_use]
+    fn next_multiple_of(self, rhs: Self) -> Self;
+
+    /// Calculates the smallest value greater than or equal to `self` that is a
+    /// multiple of `rhs`. Returns `None` if `rhs` is zero or the operation
+    /// would result in overflow.
+    #[must_use]
+    fn checked_next_multiple_of(self, rhs: Self) -> Option<Self>;
+
+    /// Rounds `self` up to a multiple of `align`, which must be a power of
+    /// two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two or the result overflows.
+    #[must_use]
+    #[inline]
+    fn align_up(self, align: Self) -> Self {
+        assert!(align.is_power_of_two(), "align must be a power of two");
+
+        self.checked_align_up(align)
+            .expect("attempt to align up with overflow")
+    }
+
+    /// Checked rounding up to a multiple of `align`, returning `None` if
+    /// `align` is not a power of two or the result overflows.
+    #[must_use]
+    #[inline]
+    fn checked_align_up(self, align: Self) -> Option<Self> {
+        if !align.is_power_of_two() {
+            return None;
+        }
+
+        let mask = align - Self::ONE;
+        Some(self.checked_add(mask)? & !mask)
+    }
+
+    /// Rounds `self` down to a multiple of `align`, which must be a power of
+    /// two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    #[inline]
+    fn align_down(self, align: Self) -> Self {
+        self.checked_align_down(align)
+            .expect("align must be a power of two")
+    }
+
+    /// Checked rounding down to a multiple of `align`, returning `None` if
+    /// `align` is not a power of two.
+    #[must_use]
+    #[inline]
+    fn checked_align_down(self, align: Self) -> Option<Self> {
+        align.is_power_of_two().then(|| self & !(align - Self::ONE))
+    }
+
+    /// Returns whether `self` is a multiple of `align`, which must be a power
+    /// of two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    #[inline]
+    fn is_aligned(self, align: Self) -> bool {
+        self.align_down(align) == self
+    }
+
+    /// Calculates the greatest common divisor of `self` and `other`.
+    ///
+    /// The result is zero only if both are zero.
+    #[must_use]
+    #[inline]
+    fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        if a == Self::ZERO {
+            return b;
+        } else if b == Self::ZERO {
+            return a;
+        }
+
+        // Take out the common factors of two, then subtract the smaller odd
+        // value from the larger until they meet.
+        let shift = (a | b).trailing_zeros();
+        a >>= a.trailing_zeros();
+        loop {
+            b >>= b.trailing_zeros();
+            if a > b {
+                (a, b) = (b, a);
+            }
+
+            b -= a;
+            if b == Self::ZERO {
+                return a << shift;
+            }
+        }
+    }
+
+    /// Calculates the least common multiple of `self` and `other`.
+    ///
+    /// The result is zero if either is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result overflows.
+    #[must_use]
+    #[inline]
+    fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other)
+            .expect("attempt to calculate lcm with overflow")
+    }
+
+    /// Checked least common multiple, returning `None` if the result overflows.
+    #[must_use]
+    #[inline]
+    fn checked_lcm(self, other: Self) -> Option<Self> {
+        if self == Self::ZERO || other == Self::ZERO {
+            return Some(Self::ZERO);
+        }
+
+        (self / self.gcd(other)).checked_mul(other)
+    }
+
+    /// Calculates the greatest common divisor of `self` and `other` along with
+    /// Bézout coefficients `x` and `y` such that `self * x + other * y == gcd`.
+    ///
+    /// The coefficients are the minimal pair found by the extended Euclidean
+    /// algorithm, with `|x|` at most half of `other / gcd` and `|y|` at most
+    /// half of `self / gcd` (or 1), so they always fit in the signed type.
+    #[must_use]
+    #[inline]
+    fn extended_gcd(self, other: Self) -> (Self, Self::S, Self::S) {
+        let (mut r0, mut r1) = (self, other);
+        let (mut x0, mut x1) = (Self::ONE, Self::ZERO);
+        let (mut y0, mut y1) = (Self::ZERO, Self::ONE);
+
+        // The coefficients are tracked in two's complement, where only the
+        // last pair, which is never returned, can overflow.
+        while r1 != Self::ZERO {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (x0, x1) = (x1, x0.wrapping_sub(q.wrapping_mul(x1)));
+            (y0, y1) = (y1, y0.wrapping_sub(q.wrapping_mul(y1)));
+        }
+
+        (r0, x0.cast_signed(), y0.cast_signed())
+    }
+
+    /// Calculates `(self + rhs) mod m` without overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    #[must_use]
+    #[inline]
+    fn mod_add(self, rhs: Self, m: Self) -> Self {
+        let (a, b) = (self % m, rhs % m);
+        let (sum, overflow) = a.overflowing_add(b);
+
+        if overflow || sum >= m {
+            sum.wrapping_sub(m)
+        } else {
+            sum
+        }
+    }
+
+    /// Calculates `(self - rhs) mod m`, which is never negative, without
+    /// overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    #[must_use]
+    #[inline]
+    fn mod_sub(self, rhs: Self, m: Self) -> Self {
+        let (a, b) = (self % m, rhs % m);
+
+        if a >= b {
+            a - b
+        } else {
+            m - (b - a)
+        }
+    }
+
+    /// Calculates `(self * rhs) mod m` without overflow.
+    ///
+    /// Uses the double-width product, which for `u128` is reduced in software.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    #[must_use]
+    #[inline]
+    fn mod_mul(self, rhs: Self, m: Self) -> Self {
+        let (a, b) = (self % m, rhs % m);
+
+        if Self::Wide::BITS > Self::BITS {
+            let p = a.cast::<Self::Wide>() * b.cast::<Self::Wide>();
+            return (p % m.cast::<Self::Wide>()).cast();
+        }
+
+        // Shift the low half into the high half, which is already less than
+        // `m`, one bit at a time.
+        let (lo, mut r) = a.widening_mul(b);
+        for i in (0..Self::BITS).rev() {
+            let carry = r >> (Self::BITS - 1) != Self::ZERO;
+            r = (r << 1_u32) | ((lo >> i) & Self::ONE);
+
+            if carry || r >= m {
+                r = r.wrapping_sub(m);
+            }
+        }
+
+        r
+    }
+
+    /// Calculates `self^exp mod m` without overflow, by squaring.
+    ///
+    /// Odd moduli use Montgomery multiplication.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    #[must_use]
+    #[inline]
+    fn mod_pow(self, exp: Self, m: Self) -> Self {
+        if let Some(mont) = Montgomery::new(m) {
+            return mont.from_montgomery(mont.pow(mont.to_montgomery(self), exp));
+        }
+
+        let mut base = self % m;
+        let mut exp = exp;
+        let mut acc = Self::ONE % m;
+        while exp != Self::ZERO {
+            if exp & Self::ONE == Self::ONE {
+                acc = acc.mod_mul(base, m);
+            }
+
+            base = base.mod_mul(base, m);
+            exp >>= 1_u32;
+        }
+
+        acc
+    }
+
+    /// Calculates the inverse of `self` modulo `m`, the `x` in `0..m` such
+    /// that `(self * x) mod m == 1 mod m`.
+    ///
+    /// Returns `None` if `self` and `m` are not coprime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is zero.
+    #[must_use]
+    #[inline]
+    fn mod_inv(self, m: Self) -> Option<Self> {
+        let (g, x, _) = (self % m).extended_gcd(m);
+        if g != Self::ONE {
+            return None;
+        }
+
+        // The coefficient is at most `m / 2` either side of zero.
+        if x < Self::S::ZERO {
+            Some(m - x.unsigned_abs().cast::<Self>())
+        } else {
+            Some(x.cast())
+        }
+    }
+
+    /// Returns whether `self` is prime.
+    ///
+    /// Uses trial division by small primes, then Miller-Rabin with bases that
+    /// are known to be correct for every value below `2^64`. Larger values use
+    /// the Baillie-PSW test, which has no known counterexample.
+    #[must_use]
+    #[inline]
+    fn is_prime(self) -> bool {
+        prime::is_prime(self)
+    }
+
+    /// Returns the smallest prime greater than or equal to `self`, or `None`
+    /// if it is greater than `MAX`.
+    #[must_use]
+    #[inline]
+    fn next_prime(self) -> Option<Self> {
+        if self <= Self::TWO {
+            return Some(Self::TWO);
+        }
+
+        // Only odd candidates from here on.
+        let mut n = self | Self::ONE;
+        while !n.is_prime() {
+            n = n.checked_add(Self::TWO)?;
+        }
+
+        Some(n)
+    }
+
+    /// Returns the largest prime less than or equal to `self`, or `None` if
+    /// `self` is less than 2.
+    #[must_use]
+    #[inline]
+    fn prev_prime(self) -> Option<Self> {
+        if self < Self::TWO {
+            return None;
+        } else if self < Self::TWO + Self::ONE {
+            return Some(Self::TWO);
+        }
+
+        // Only odd candidates from here on, and 3 is prime.
+        let mut n = self - (Self::ONE - (self & Self::ONE));
+        while !n.is_prime() {
+            n -= Self::TWO;
+        }
+
+        Some(n)
+    }
+
+    /// Returns the prime factorization of `self`, without allocating.
+    ///
+    /// Small factors are found by trial division and the rest with Brent's
+    /// variant of Pollard's rho, which takes time in the order of the square
+    /// root of the second largest prime factor. Zero and one have no prime
+    /// factors.
+    #[must_use]
+    #[inline]
+    fn factorize(self) -> Factorization<Self> {
+        Factorization::new(self)
+    }
+
+    /// Returns an iterator over the divisors of `self`, starting with 1, in
+    /// no particular order.
+    ///
+    /// Zero has no divisors here, though every integer divides it.
+    #[must_use]
+    #[inline]
+    fn divisors(self) -> Divisors<Self> {
+        Divisors::new(self)
+    }
+
+    /// Returns the number of integers from 1 to `self` that are coprime to
+    /// `self`, which is zero for zero.
+    #[must_use]
+    #[inline]
+    fn euler_totient(self) -> Self {
+        self.factorize()
+            .iter()
+            .fold(self, |acc, &(p, _)| acc / p * (p - Self::ONE))
+    }
+
+    /// Returns whether no square greater than 1 divides `self`.
+    ///
+    /// Zero is divisible by every square, so is not square-free.
+    #[must_use]
+    #[inline]
+    fn is_square_free(self) -> bool {
+        self != Self::ZERO && self.factorize().iter().all(|&(_, e)| e == 1)
+    }
+
+    /// Calculates `self + rhs + carry`, returning the sum and the carry out.
+    ///
+    /// Chaining this from the least significant limb adds multi-limb numbers.
+    #[must_use]
+    #[inline]
+    fn carrying_add(self, rhs: Self, carry: bool) -> (Self, bool) {
+        let (a, b) = self.overflowing_add(rhs);
+        let (c, d) = a.overflowing_add(if carry { Self::ONE } else { Self::ZERO });
+
+        (c, b | d)
+    }
+
+    /// Calculates `self - rhs - borrow`, returning the difference and the
+    /// borrow out.
+    ///
+    /// Chaining this from the least significant limb subtracts multi-limb
+    /// numbers.
+    #[must_use]
+    #[inline]
+    fn borrowing_sub(self, rhs: Self, borrow: bool) -> (Self, bool) {
+        let (a, b) = self.overflowing_sub(rhs);
+        let (c, d) = a.overflowing_sub(if borrow { Self::ONE } else { Self::ZERO });
+
+        (c, b | d)
+    }
+
+    /// Calculates the complete `self * rhs + carry` without overflow,
+    /// returning the low-order and high-order bits.
+    ///
+    /// Chaining this from the least significant limb, with the high-order
+    /// bits as the next carry, multiplies a multi-limb number by one limb.
+    #[must_use]
+    #[inline]
+    fn carrying_mul(self, rhs: Self, carry: Self) -> (Self, Self) {
+        self.carrying_mul_add(rhs, carry, Self::ZERO)
+    }
+
+    /// Calculates the complete `self * rhs + carry + add` without overflow,
+    /// returning the low-order and high-order bits.
+    ///
+    /// The result always fits, as `MAX * MAX + MAX + MAX` is the largest
+    /// double-width value.
+    #[must_use]
+    #[inline]
+    fn carrying_mul_add(self, rhs: Self, carry: Self, add: Self) -> (Self, Self) {
+        let (lo, hi) = self.widening_mul(rhs);
+        let (lo, a) = lo.overflowing_add(carry);
+        let (lo, b) = lo.overflowing_add(add);
+
+        // The complete result fits, so adding the carries cannot overflow.
+        let hi = hi + if a { Self::ONE } else { Self::ZERO };
+        let hi = hi + if b { Self::ONE } else { Self::ZERO };
+
+        (lo, hi)
+    }
+
+    /// Deposits the low bits of `self` into the set bits of `mask`, from the
+    /// least significant up, clearing every other bit.
+    ///
+    /// This is the portable equivalent of the x86 BMI2 `pdep` instruction.
+    #[must_use]
+    fn pdep(self, mask: Self) -> Self {
+        let mut mask = mask;
+        let mut bit = Self::ONE;
+        let mut result = Self::ZERO;
+
+        while mask != Self::ZERO {
+            let lowest = mask & !(mask - Self::ONE);
+            if self & bit != Self::ZERO {
+                result |= lowest;
+            }
+
+            mask ^= lowest;
+            bit <<= 1_u32;
+        }
+
+        result
+    }
+
+    /// Extracts the bits of `self` at the set bits of `mask` into the low
+    /// bits of the result, from the least significant up.
+    ///
+    /// This is the portable equivalent of the x86 BMI2 `pext` instruction.
+    #[must_use]
+    fn pext(self, mask: Self) -> Self {
+        let mut mask = mask;
+        let mut bit = Self::ONE;
+        let mut result = Self::ZERO;
+
+        while mask != Self::ZERO {
+            let lowest = mask & !(mask - Self::ONE);
+            if self & lowest != Self::ZERO {
+                result |= bit;
+            }
+
+            mask ^= lowest;
+            bit <<= 1_u32;
+        }
+
+        result
+    }
+
+    /// Returns the number of set bits below bit `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is greater than `BITS`.
+    #[must_use]
+    #[inline]
+    fn rank(self, i: u32) -> u32 {
+        (self & Self::low_mask(i)).count_ones()
+    }
+
+    /// Returns the index of the set bit with `k` set bits below it, or `None`
+    /// if `self` has no more than `k` set bits.
+    ///
+    /// Finds it with a binary search over the halves of `self`, so that
+    /// `self.rank(self.select(k).unwrap()) == k`.
+    #[must_use]
+    fn select(self, k: u32) -> Option<u32> {
+        if k >= self.count_ones() {
+            return None;
+        }
+
+        let (mut start, mut len, mut k) = (0, Self::BITS, k);
+        while len > 1 {
+            let half = len / 2;
+            let ones = self.extract_bits(start, half).count_ones();
+
+            if k < ones {
+                len = half;
+            } else {
+                k -= ones;
+                start += half;
+                len -= half;
+            }
+        }
+
+        Some(start)
+    }
+
+    /// Returns the next larger value with the same number of set bits, or
+    /// `None` if there is none or `self` is zero.
+    ///
+    /// Uses Gosper's hack, so starting from `low_mask(k)` steps through every
+    /// `k`-bit value in ascending order.
+    #[must_use]
+    #[inline]
+    fn next_same_popcount(self) -> Option<Self> {
+        if self == Self::ZERO {
+            return None;
+        }
+
+        // Move the lowest run of set bits' top bit up by one, and the rest
+        // of the run down to the bottom.
+        let lowest = self & !(self - Self::ONE);
+        let ripple = self.checked_add(lowest)?;
+        let ones = ((self ^ ripple) >> 2_u32) >> self.trailing_zeros();
+
+        Some(ripple | ones)
+    }
+
+    /// Returns an iterator over every submask of `self`, the values whose set
+    /// bits are all set in `self`, from `self` down to zero.
+    ///
+    /// There are `2^count_ones` of them, which is commonly used to walk the
+    /// subsets of a set in bitmask dynamic programming.
+    #[must_use]
+    #[inline(always)]
+    fn submasks(self) -> Submasks<Self> {
+        Submasks::new(self)
+    }
+
+    /// Returns an iterator over every value with exactly `k` of its lowest `n`
+    /// bits set and no others, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `BITS` or `k` is greater than `n`.
+    #[must_use]
+    #[inline]
+    fn subsets(n: u32, k: u32) -> Subsets<Self> {
+        Subsets::new(n, k)
+    }
+
+    /// Converts `self` to its binary reflected Gray code, in which
+    /// consecutive values differ by one bit.
+    #[must_use]
+    #[inline(always)]
+    fn to_gray(self) -> Self {
+        self ^ (self >> 1_u32)
+    }
+
+    /// Converts a binary reflected Gray code back to its value, undoing
+    /// `to_gray`.
+    #[must_use]
+    #[inline]
+    fn from_gray(gray: Self) -> Self {
+        // Each bit is the parity of the bits from it up, which takes a prefix
+        // `xor` over doubling distances.
+        let mut x = gray;
+        let mut shift = 1;
+        while shift < Self::BITS {
+            x ^= x >> shift;
+            shift *= 2;
+        }
+
+        x
+    }
+
+    /// Interleaves the bits of `x` and `y` into a 2D Morton (Z-order) code,
+    /// with `x` in the even bits and `y` in the odd bits.
+    ///
+    /// If `W` has fewer than twice the bits of `Self`, only the low
+    /// `W::BITS / 2` bits of each coordinate are used.
+    #[must_use]
+    #[inline]
+    fn morton_encode_2d<W: UnsignedInt>(x: Self, y: Self) -> W {
+        let bits = morton::coordinate_bits::<Self, W>(2);
+
+        morton::spread(x.cast::<W>(), 2, bits) | (morton::spread(y.cast::<W>(), 2, bits) << 1_u32)
+    }
+
+    /// Splits a 2D Morton (Z-order) code back into its `x` and `y`
+    /// coordinates, undoing `morton_encode_2d`.
+    #[must_use]
+    #[inline]
+    fn morton_decode_2d<W: UnsignedInt>(code: W) -> (Self, Self) {
+        let bits = morton::coordinate_bits::<Self, W>(2);
+
+        (
+            morton::compact(code, 2, bits).cast(),
+            morton::compact(code >> 1_u32, 2, bits).cast(),
+        )
+    }
+
+    /// Interleaves the bits of `x`, `y` and `z` into a 3D Morton (Z-order)
+    /// code, with bit `i` of `x` at bit `3 * i`, followed by `y` and `z`.
+    ///
+    /// If `W` has fewer than three times the bits of `Self`, only the low
+    /// `W::BITS / 3` bits of each coordinate are used, such as 21 bits of
+    /// each `u32` in a `u64` code.
+    #[must_use]
+    #[inline]
+    fn morton_encode_3d<W: UnsignedInt>(x: Self, y: Self, z: Self) -> W {
+        let bits = morton::coordinate_bits::<Self, W>(3);
+
+        morton::spread(x.cast::<W>(), 3, bits)
+            | (morton::spread(y.cast::<W>(), 3, bits) << 1_u32)
+            | (morton::spread(z.cast::<W>(), 3, bits) << 2_u32)
+    }
+
+    /// Splits a 3D Morton (Z-order) code back into its `x`, `y` and `z`
+    /// coordinates, undoing `morton_encode_3d`.
+    #[must_use]
+    #[inline]
+    fn morton_decode_3d<W: UnsignedInt>(code: W) -> (Self, Self, Self) {
+        let bits = morton::coordinate_bits::<Self, W>(3);
+
+        (
+            morton::compact(code, 3, bits).cast(),
+            morton::compact(code >> 1_u32, 3, bits).cast(),
+            morton::compact(code >> 2_u32, 3, bits).cast(),
+        )
+    }
 }
 
 macro_rules! impl_unsigned_integer {
@@ -92,6 +746,36 @@ macro_rules! impl_unsigned_integer {
                 fn cast_signed(self) -> Self::S {
                     self.cast_signed()
                 }
+
+                #[inline(always)]
+                fn is_power_of_two(self) -> bool {
+                    self.is_power_of_two()
+                }
+
+                #[inline(always)]
+                fn next_power_of_two(self) -> Self {
+                    self.next_power_of_two()
+                }
+
+                #[inline(always)]
+                fn checked_next_power_of_two(self) -> Option<Self> {
+                    self.checked_next_power_of_two()
+                }
+
+                #[inline(always)]
+                fn div_ceil(self, rhs: Self) -> Self {
+                    self.div_ceil(rhs)
+                }
+
+                #[inline(always)]
+                fn next_multiple_of(self, rhs: Self) -> Self {
+                    self.next_multiple_of(rhs)
+                }
+
+                #[inline(always)]
+                fn checked_next_multiple_of(self, rhs: Self) -> Option<Self> {
+                    self.checked_next_multiple_of(rhs)
+                }
             }
         )*
     };
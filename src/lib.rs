@@ -4,13 +4,22 @@ mod int;
 mod num;
 
 pub use bit::{Bit, BitAssignOps, BitOps};
-pub use float::{Float, FloatConst};
-pub use int::{Int, SignedInt, UnsignedInt};
-pub use num::{Num, NumAssignOps, NumOps, NumOrd};
+#[doc(hidden)]
+pub use float::{__ApproxOperand, __assert_approx_eq};
+pub use float::{
+    Float, FloatBuf, FloatConst, FloatDisplay, FloatFormat, HexFloatBuf, RoundingMode, Tolerance,
+};
+pub use int::{
+    BitIndices, Divisors, Factorization, FromIntError, Int, Montgomery, RadixBuf, RadixDisplay,
+    RadixOptions, SignedInt, Submasks, Subsets, UnsignedInt,
+};
+pub use num::{Num, NumAssignOps, NumOps, NumOrd, ParseNumError};
 
+mod as_primitive;
 mod min_max;
 mod signed;
 
+pub use as_primitive::AsPrimitive;
 pub use min_max::MinMax;
 pub use signed::Signed;
 
@@ -1,9 +1,12 @@
+#[allow(clippy::module_inception)]
 pub mod num;
 pub mod num_assign_ops;
 pub mod num_ops;
 pub mod num_ord;
+pub mod parse_num_error;
 
 pub use num::Num;
 pub use num_assign_ops::NumAssignOps;
 pub use num_ops::NumOps;
 pub use num_ord::NumOrd;
+pub use parse_num_error::ParseNumError;
@@ -1,6 +1,6 @@
-use crate::MinMax;
+use crate::{AsPrimitive, MinMax};
 
-use super::{NumAssignOps, NumOps, NumOrd};
+use super::{NumAssignOps, NumOps, NumOrd, ParseNumError};
 
 /// Numerical types.
 ///
@@ -8,13 +8,53 @@ use super::{NumAssignOps, NumOps, NumOrd};
 /// - Non-assignming arithmetic operations
 /// - Assigning arithmetic operations
 /// - Partial comparisons
-pub trait Num: Copy + PartialEq + MinMax + NumAssignOps + NumOps + NumOrd {
+/// - Primitive casting to and from any other `Num` type
+pub trait Num:
+    Copy
+    + PartialEq
+    + MinMax
+    + NumAssignOps
+    + NumOps
+    + NumOrd
+    + AsPrimitive<i8>
+    + AsPrimitive<i16>
+    + AsPrimitive<i32>
+    + AsPrimitive<i64>
+    + AsPrimitive<i128>
+    + AsPrimitive<isize>
+    + AsPrimitive<u8>
+    + AsPrimitive<u16>
+    + AsPrimitive<u32>
+    + AsPrimitive<u64>
+    + AsPrimitive<u128>
+    + AsPrimitive<usize>
+    + AsPrimitive<f32>
+    + AsPrimitive<f64>
+{
     const ZERO: Self;
 
     const ONE: Self;
 
     const TWO: Self;
 
+    /// Converts `x` to `Self` as if by `x as Self`.
+    #[must_use]
+    fn cast_from<T: Num>(x: T) -> Self;
+
+    /// Converts `self` to `T` as if by `self as T`.
+    #[must_use]
+    #[inline(always)]
+    fn cast<T: Num>(self) -> T {
+        T::cast_from(self)
+    }
+
+    /// Parses a decimal number from a string.
+    ///
+    /// Integers accept an optional sign followed by decimal digits. Floats
+    /// accept anything `f64::from_str` does, including exponents, `inf` and
+    /// `NaN`.
+    fn from_str_dec(s: &str) -> Result<Self, ParseNumError>;
+
     /// Returns the difference of `self` and `other`.
     ///
     /// The purpose of this function is to safely subtract two types without
@@ -50,6 +90,20 @@ macro_rules! impl_num_u {
 
                 const TWO: Self = 2 as Self;
 
+                #[inline(always)]
+                fn cast_from<T: Num>(x: T) -> Self {
+                    <T as AsPrimitive<Self>>::as_(x)
+                }
+
+                #[inline]
+                fn from_str_dec(s: &str) -> Result<Self, ParseNumError> {
+                    if s.is_empty() {
+                        return Err(ParseNumError::Empty);
+                    }
+
+                    s.parse::<Self>().map_err(ParseNumError::from)
+                }
+
                 #[cfg(feature = "ex")]
                 #[inline(always)]
                 fn diff(self, other: Self) -> Self {
@@ -78,6 +132,20 @@ macro_rules! impl_num_s {
 
                 const TWO: Self = 2 as Self;
 
+                #[inline(always)]
+                fn cast_from<T: Num>(x: T) -> Self {
+                    <T as AsPrimitive<Self>>::as_(x)
+                }
+
+                #[inline]
+                fn from_str_dec(s: &str) -> Result<Self, ParseNumError> {
+                    if s.is_empty() {
+                        return Err(ParseNumError::Empty);
+                    }
+
+                    s.parse::<Self>().map_err(ParseNumError::from)
+                }
+
                 #[cfg(feature = "ex")]
                 #[inline(always)]
                 fn diff(self, other: Self) -> Self {
@@ -0,0 +1,63 @@
+use std::{
+    error::Error,
+    fmt,
+    num::{IntErrorKind, ParseFloatError, ParseIntError},
+};
+
+/// The error returned when parsing a `Num` from a string fails.
+///
+/// The same error is used for every `Num` type so generic code can handle
+/// failures without knowing what type it parsed.
+///
+/// See [`Num::from_str_dec`] or [`Int::from_str_radix`].
+///
+/// [`Num::from_str_dec`]: crate::Num::from_str_dec
+/// [`Int::from_str_radix`]: crate::Int::from_str_radix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseNumError {
+    /// The string is empty.
+    Empty,
+
+    /// The string contains a character that is not a valid digit.
+    InvalidDigit,
+
+    /// The value is greater than the type's `MinMax::MAX`.
+    Overflow,
+
+    /// The value is less than the type's `MinMax::MIN`.
+    Underflow,
+
+    /// The string is not a valid floating point literal.
+    InvalidFloat,
+}
+
+impl fmt::Display for ParseNumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("cannot parse number from empty string"),
+            Self::InvalidDigit => f.write_str("invalid digit found in string"),
+            Self::Overflow => f.write_str("number too large to fit in target type"),
+            Self::Underflow => f.write_str("number too small to fit in target type"),
+            Self::InvalidFloat => f.write_str("invalid float literal"),
+        }
+    }
+}
+
+impl Error for ParseNumError {}
+
+impl From<ParseIntError> for ParseNumError {
+    fn from(e: ParseIntError) -> Self {
+        match e.kind() {
+            IntErrorKind::Empty => Self::Empty,
+            IntErrorKind::PosOverflow => Self::Overflow,
+            IntErrorKind::NegOverflow => Self::Underflow,
+            _ => Self::InvalidDigit,
+        }
+    }
+}
+
+impl From<ParseFloatError> for ParseNumError {
+    fn from(_: ParseFloatError) -> Self {
+        Self::InvalidFloat
+    }
+}